@@ -0,0 +1,394 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{get_index, indices, Board, Color, Piece, Setup, Square};
+use crate::rules::{self, CapturePolicy, Direction, RuleSet};
+
+/// A board diagram, one line per row with the squares separated by spaces:
+///
+/// - `'` is a square that is not played on,
+/// - `*` is an empty playable square,
+/// - `w` and `W` are a white man and king,
+/// - `b` and `B` are a black man and king.
+///
+/// The rows may be followed by a line naming the rules and the side to
+/// move, such as `russian, black to move`. Without it the game is American
+/// checkers with White to move.
+///
+/// Anything else that differs from a game that has just started follows
+/// on lines of its own, with squares given by their algebraic names:
+///
+/// - `setup: white on top, black moves first`,
+/// - `captures: optional` or `captures: huffing`,
+/// - `capturing: d4 up left`, the piece in the middle of a capture
+///   sequence and the direction of its last jump,
+/// - `captured: c3 e5`, the pieces captured so far in the turn,
+/// - `huffable: a3`, the pieces that skipped a mandatory capture,
+/// - `white streak: d4 2`, the king that made the last non-capturing
+///   moves of a side and how many of them it made in a row.
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    fn from_str(diagram: &str) -> Result<Board, ParseBoardError> {
+        let mut lines: Vec<_> = diagram
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let state = match lines.iter().position(|line| line.contains(':')) {
+            Some(start) => lines.split_off(start),
+            None => vec![],
+        };
+        let (rules, turn) = match lines.last().and_then(|line| line.strip_suffix(" to move")) {
+            Some(footer) => {
+                let (name, turn) = footer
+                    .split_once(',')
+                    .ok_or_else(|| ParseBoardError::BadFooter(footer.to_string()))?;
+                let rules = rules::get_rules(name.trim())
+                    .ok_or_else(|| ParseBoardError::UnknownRules(name.trim().to_string()))?;
+                let turn = read_color(turn.trim())
+                    .ok_or_else(|| ParseBoardError::BadFooter(footer.to_string()))?;
+                lines.pop();
+                (rules, turn)
+            }
+            None => (&rules::American as &'static dyn RuleSet, Color::White),
+        };
+        let mut board = Board::read_rows(rules, &lines)?.with_turn(turn);
+        for line in state {
+            board
+                .read_state(line)
+                .ok_or_else(|| ParseBoardError::BadState(line.to_string()))?;
+        }
+        if board.capturing.is_some() {
+            board.next_landings = board.get_landings();
+            if board.next_landings == 0 {
+                return Err(ParseBoardError::CaptureOver);
+            }
+        }
+        Ok(board)
+    }
+}
+
+/// Writes the board as a diagram that `FromStr` reads back, followed by
+/// the rules, the side to move and the rest of the state of the game.
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self.size();
+        for row in 0..size {
+            for col in 0..size {
+                if col > 0 {
+                    f.write_str(" ")?;
+                }
+                let symbol = match get_index(self.rules, row, col) {
+                    None => "'",
+                    Some(index) => match self.piece_at(index) {
+                        None => "*",
+                        Some(Piece { color, is_king }) => match (color, is_king) {
+                            (Color::White, false) => "w",
+                            (Color::White, true) => "W",
+                            (Color::Black, false) => "b",
+                            (Color::Black, true) => "B",
+                        },
+                    },
+                };
+                f.write_str(symbol)?;
+            }
+            f.write_str("\n")?;
+        }
+        write!(
+            f,
+            "{}, {} to move",
+            self.rules.name(),
+            color_name(self.turn)
+        )?;
+        if self.setup != Setup::default() {
+            write!(
+                f,
+                "\nsetup: {} on top, {} moves first",
+                color_name(self.setup.top_color),
+                color_name(self.setup.first_mover)
+            )?;
+        }
+        match self.capture_policy {
+            CapturePolicy::Mandatory => {}
+            CapturePolicy::Optional => f.write_str("\ncaptures: optional")?,
+            CapturePolicy::Huffing => f.write_str("\ncaptures: huffing")?,
+        }
+        if let (Some(square), Some(direction)) = (self.capturing, self.capture_direction) {
+            write!(
+                f,
+                "\ncapturing: {} {}",
+                self.square_name(square),
+                direction_name(direction)
+            )?;
+        }
+        if self.captured != 0 {
+            write!(f, "\ncaptured: {}", self.square_names(self.captured))?;
+        }
+        if self.huffable != 0 {
+            write!(f, "\nhuffable: {}", self.square_names(self.huffable))?;
+        }
+        for &color in [Color::White, Color::Black].iter() {
+            if let Some((king, moves)) = self.king_streaks[color as usize] {
+                write!(
+                    f,
+                    "\n{} streak: {} {}",
+                    color_name(color),
+                    self.square_name(king),
+                    moves
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Board {
+    /// Reads the rows of a diagram, see `FromStr`, for a board played by the
+    /// given rules with White to move.
+    pub(crate) fn read_rows(
+        rules: &'static dyn RuleSet,
+        rows: &[&str],
+    ) -> Result<Board, ParseBoardError> {
+        let size = rules.size();
+        if rows.len() != size {
+            return Err(ParseBoardError::WrongRowCount {
+                expected: size,
+                found: rows.len(),
+            });
+        }
+        let mut board = Board::empty(rules, Setup::new(Color::White, Color::Black));
+        for (row, line) in rows.iter().enumerate() {
+            let symbols: Vec<_> = line.split_ascii_whitespace().collect();
+            if symbols.len() != size {
+                return Err(ParseBoardError::WrongColumnCount {
+                    row,
+                    expected: size,
+                    found: symbols.len(),
+                });
+            }
+            for (col, symbol) in symbols.into_iter().enumerate() {
+                let piece = match symbol {
+                    "'" | "*" => None,
+                    "w" => Some((Color::White, false)),
+                    "W" => Some((Color::White, true)),
+                    "b" => Some((Color::Black, false)),
+                    "B" => Some((Color::Black, true)),
+                    _ => {
+                        return Err(ParseBoardError::UnknownSymbol {
+                            row,
+                            col,
+                            symbol: symbol.to_string(),
+                        })
+                    }
+                };
+                match (get_index(rules, row, col), symbol) {
+                    (None, "'") | (Some(_), "*") => {}
+                    (None, "*") | (Some(_), "'") => {
+                        return Err(ParseBoardError::WrongSquare { row, col })
+                    }
+                    (None, _) => return Err(ParseBoardError::PieceOnLightSquare { row, col }),
+                    (Some(index), _) => board.put(
+                        index,
+                        piece.map(|(color, is_king)| Piece { color, is_king }),
+                    ),
+                }
+            }
+        }
+        Ok(board)
+    }
+
+    /// Reads a line describing the state of the game, see `FromStr`.
+    fn read_state(&mut self, line: &str) -> Option<()> {
+        let (key, value) = line.split_once(':')?;
+        let mut words = value.split_whitespace();
+        match key.trim() {
+            "setup" => {
+                let (top, first) = value.split_once(',')?;
+                let top_color = read_color(top.trim().strip_suffix(" on top")?)?;
+                let first_mover = read_color(first.trim().strip_suffix(" moves first")?)?;
+                self.setup = Setup::new(first_mover, top_color);
+            }
+            "captures" => {
+                self.capture_policy = match value.trim() {
+                    "mandatory" => CapturePolicy::Mandatory,
+                    "optional" => CapturePolicy::Optional,
+                    "huffing" => CapturePolicy::Huffing,
+                    _ => return None,
+                }
+            }
+            "capturing" => {
+                let square = self.read_square(words.next()?, self.turn, None)?;
+                let direction = words.collect::<Vec<_>>().join(" ");
+                let (direction, _) = DIRECTION_NAMES
+                    .iter()
+                    .find(|(_, name)| *name == direction)?;
+                self.capturing = Some(square);
+                self.capture_direction = Some(*direction);
+            }
+            "captured" => {
+                for name in words {
+                    let square = self.read_square(name, self.turn.opponent(), None)?;
+                    self.captured |= 1 << self.index_of(square);
+                }
+            }
+            "huffable" => {
+                for name in words {
+                    let square = self.read_square(name, self.turn.opponent(), None)?;
+                    self.huffable |= 1 << self.index_of(square);
+                }
+            }
+            key => {
+                let color = read_color(key.strip_suffix(" streak")?)?;
+                let king = self.read_square(words.next()?, color, Some(true))?;
+                let moves = words.next()?.parse().ok()?;
+                if words.next().is_some() {
+                    return None;
+                }
+                self.king_streaks[color as usize] = Some((king, moves));
+            }
+        }
+        Some(())
+    }
+
+    /// Reads the name of a square holding a piece of the given color, and
+    /// of the given kind if there is one.
+    fn read_square(&self, name: &str, color: Color, is_king: Option<bool>) -> Option<Square> {
+        let square = Square::from_name(self.rules, name).ok()?;
+        match self.piece_at(self.index_of(square)) {
+            Some(piece)
+                if piece.color == color && is_king.unwrap_or(piece.is_king) == piece.is_king =>
+            {
+                Some(square)
+            }
+            _ => None,
+        }
+    }
+
+    fn square_name(&self, square: Square) -> String {
+        square.name(self.rules).expect("squares are on the board")
+    }
+
+    /// The names of the squares in the set, separated by spaces.
+    fn square_names(&self, squares: u64) -> String {
+        indices(squares)
+            .map(|index| {
+                let (row, col) = self.geometry.position(index);
+                self.square_name(Square::at(row, col))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// How directions are written, rows counting down from the top.
+const DIRECTION_NAMES: [((Direction, Direction), &str); 8] = {
+    use Direction::*;
+    [
+        ((Decrease, Decrease), "up left"),
+        ((Decrease, Keep), "up"),
+        ((Decrease, Increase), "up right"),
+        ((Keep, Decrease), "left"),
+        ((Keep, Increase), "right"),
+        ((Increase, Decrease), "down left"),
+        ((Increase, Keep), "down"),
+        ((Increase, Increase), "down right"),
+    ]
+};
+
+fn direction_name(direction: (Direction, Direction)) -> &'static str {
+    DIRECTION_NAMES
+        .iter()
+        .find(|(other, _)| *other == direction)
+        .map(|(_, name)| *name)
+        .expect("every direction has a name")
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+    }
+}
+
+fn read_color(name: &str) -> Option<Color> {
+    match name {
+        "white" => Some(Color::White),
+        "black" => Some(Color::Black),
+        _ => None,
+    }
+}
+
+/// Why a board diagram could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseBoardError {
+    /// The diagram does not have a row for every row of the board.
+    WrongRowCount { expected: usize, found: usize },
+    /// A row does not have a symbol for every square.
+    WrongColumnCount {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A symbol is not one of the known ones.
+    UnknownSymbol {
+        row: usize,
+        col: usize,
+        symbol: String,
+    },
+    /// A playable square is marked as unplayable or the other way round.
+    WrongSquare { row: usize, col: usize },
+    /// A piece stands on a square that is not played on.
+    PieceOnLightSquare { row: usize, col: usize },
+    /// The line after the rows does not name the rules and the side to
+    /// move.
+    BadFooter(String),
+    /// No ruleset has the given name.
+    UnknownRules(String),
+    /// A line describing the state of the game can not be read or does
+    /// not fit the pieces on the board.
+    BadState(String),
+    /// A piece is said to be in the middle of a capture, but can not
+    /// capture any further.
+    CaptureOver,
+}
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBoardError::WrongRowCount { expected, found } => {
+                write!(f, "Expected {} rows, found {}", expected, found)
+            }
+            ParseBoardError::WrongColumnCount {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Expected {} squares in row {}, found {}",
+                expected, row, found
+            ),
+            ParseBoardError::UnknownSymbol { row, col, symbol } => {
+                write!(f, "Unknown symbol {:?} at ({}, {})", symbol, row, col)
+            }
+            ParseBoardError::WrongSquare { row, col } => {
+                write!(f, "The square at ({}, {}) is marked wrongly", row, col)
+            }
+            ParseBoardError::PieceOnLightSquare { row, col } => {
+                write!(
+                    f,
+                    "The piece at ({}, {}) is not on a played square",
+                    row, col
+                )
+            }
+            ParseBoardError::BadFooter(footer) => write!(f, "Can not read {:?}", footer),
+            ParseBoardError::UnknownRules(name) => write!(f, "Unknown rules {:?}", name),
+            ParseBoardError::BadState(line) => write!(f, "Can not read {:?}", line),
+            ParseBoardError::CaptureOver => {
+                write!(f, "The capturing piece can not capture any further")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseBoardError {}
@@ -0,0 +1,155 @@
+use std::sync::OnceLock;
+
+use super::{count_squares, get_index, get_position, Layout, MAX_SQUARES};
+use crate::rules::{Direction, RuleSet};
+
+/// The largest board whose playable squares fit in a bitboard.
+const MAX_SIZE: usize = 11;
+
+/// How the playable squares of a board map to the bits of a bitboard, where
+/// bit `i` stands for the square with index `i`.
+///
+/// A step in a given direction moves a bit by the same amount for every
+/// square in rows of the same parity, so whole sets of squares can be moved
+/// with a mask and a shift.
+///
+/// Bitboards are `u64` rather than `u32`, as 32 bits only cover the 8x8
+/// board of dark squares. The 10x10 boards have 50 playable squares and the
+/// Turkish board plays on all 64.
+pub(crate) struct Geometry {
+    /// Every playable square.
+    pub(crate) squares: u64,
+    /// For every direction and row parity, the squares a step in that
+    /// direction does not leave the board from and how far it moves them.
+    steps: [[(u64, i32); 2]; 9],
+    /// The row and column of every square.
+    positions: [(usize, usize); MAX_SQUARES],
+}
+
+impl Geometry {
+    /// The geometry of the boards the given rules are played on.
+    ///
+    /// Panics if the board does not fit in a bitboard, see `RuleSet::size`.
+    pub(crate) fn for_rules(rules: &dyn RuleSet) -> &'static Geometry {
+        assert!(
+            rules.size() <= MAX_SIZE && count_squares(rules) <= MAX_SQUARES,
+            "a {0}x{0} board does not fit in a bitboard",
+            rules.size()
+        );
+        static GEOMETRIES: [[OnceLock<Geometry>; MAX_SIZE + 1]; 3] =
+            [const { [const { OnceLock::new() }; MAX_SIZE + 1] }; 3];
+        let layout = rules.layout();
+        GEOMETRIES[layout as usize][rules.size()].get_or_init(|| Geometry::new(rules))
+    }
+
+    fn new(rules: &dyn RuleSet) -> Geometry {
+        let size = rules.size();
+        let mut geometry = Geometry {
+            squares: 0,
+            steps: [[(0, 0); 2]; 9],
+            positions: [(0, 0); MAX_SQUARES],
+        };
+        for index in 0..count_squares(rules) {
+            let (row, col) = get_position(rules, index);
+            geometry.squares |= 1 << index;
+            geometry.positions[index] = (row, col);
+            for &direction in DIRECTIONS.iter() {
+                let next = get_next(rules.layout(), size, row, col, direction)
+                    .and_then(|(row, col)| get_index(rules, row, col));
+                if let Some(next) = next {
+                    let step = &mut geometry.steps[direction_index(direction)][row % 2];
+                    step.0 |= 1 << index;
+                    step.1 = next as i32 - index as i32;
+                }
+            }
+        }
+        geometry
+    }
+
+    /// Moves every square in `squares` one step in the given direction,
+    /// dropping the ones that would leave the board.
+    pub(crate) fn shift(&self, squares: u64, direction: (Direction, Direction)) -> u64 {
+        self.steps[direction_index(direction)]
+            .iter()
+            .map(|&(from, by)| {
+                let squares = squares & from;
+                if by >= 0 {
+                    squares << by
+                } else {
+                    squares >> -by
+                }
+            })
+            .fold(0, |result, squares| result | squares)
+    }
+
+    /// The index of the square one step away from `index` in the given
+    /// direction, if it is on the board.
+    pub(crate) fn step(&self, index: usize, direction: (Direction, Direction)) -> Option<usize> {
+        match self.shift(1 << index, direction) {
+            0 => None,
+            next => Some(next.trailing_zeros() as usize),
+        }
+    }
+
+    /// The row and column of the square with the given index.
+    pub(crate) fn position(&self, index: usize) -> (usize, usize) {
+        self.positions[index]
+    }
+}
+
+/// Returns the indices of the squares in the set, lowest first.
+pub(crate) fn indices(mut squares: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if squares == 0 {
+            None
+        } else {
+            let index = squares.trailing_zeros() as usize;
+            squares &= squares - 1;
+            Some(index)
+        }
+    })
+}
+
+/// Every direction a piece can move in, diagonal and orthogonal.
+pub(crate) const DIRECTIONS: [(Direction, Direction); 8] = {
+    use Direction::*;
+    [
+        (Decrease, Decrease),
+        (Decrease, Keep),
+        (Decrease, Increase),
+        (Keep, Decrease),
+        (Keep, Increase),
+        (Increase, Decrease),
+        (Increase, Keep),
+        (Increase, Increase),
+    ]
+};
+
+fn direction_index((row, col): (Direction, Direction)) -> usize {
+    row as usize * 3 + col as usize
+}
+
+/// Returns the square a step away in the given direction. Orthogonal
+/// neighbours on a board of dark squares are two squares away.
+fn get_next(
+    layout: Layout,
+    size: usize,
+    row: usize,
+    col: usize,
+    (row_direction, col_direction): (Direction, Direction),
+) -> Option<(usize, usize)> {
+    use Direction::*;
+    let orthogonal = row_direction == Keep || col_direction == Keep;
+    let step = if orthogonal && layout != Layout::Full {
+        2
+    } else {
+        1
+    };
+    let next = |value: usize, direction: Direction| match direction {
+        Increase if value + step < size => Some(value + step),
+        Decrease => value.checked_sub(step),
+        Keep => Some(value),
+        _ => None,
+    };
+    Some((next(row, row_direction)?, next(col, col_direction)?))
+}
@@ -1,62 +1,177 @@
-use std::convert::TryFrom;
-use std::usize;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use wasm_bindgen::prelude::*;
 
-use crate::rules::{self, Movement, MovementType};
+use crate::rules::{
+    self, CapturePolicy, Crowning, Direction, LegalMove, MoveSequence, Movement, MovementType,
+    RuleSet,
+};
+use crate::utils::to_byte;
+
+mod diagram;
+mod geometry;
+mod square;
+mod symmetry;
+mod zobrist;
+
+pub use diagram::ParseBoardError;
+pub(crate) use geometry::{indices, Geometry, DIRECTIONS};
+pub use square::Square;
+pub use symmetry::{Symmetry, SYMMETRIES};
+
+/// The number of squares a board can hold pieces on.
+pub const MAX_SQUARES: usize = 64;
 
 #[derive(Clone, Copy)]
 pub struct Board {
-    pub(crate) squares: [[Option<Piece>; 4]; 8],
+    /// The squares holding pieces of each color, indexed by color. Bit `i`
+    /// stands for the playable square with index `i`, counted row by row.
+    pub(crate) pieces: [u64; 2],
+    /// The squares holding kings of either color.
+    pub(crate) kings: u64,
+    /// The Zobrist hash of the pieces and the side to move, kept up to date
+    /// as they change.
+    pub(crate) zobrist: u64,
+    /// The piece that is in the middle of a capture sequence, if any.
+    /// While it is set, only that piece may move and only by capturing.
+    pub(crate) capturing: Option<Square>,
+    /// The squares the piece in the middle of a capture sequence may land
+    /// on with its next jump, worked out when it made the previous one. It
+    /// follows from the rest of the position, so equality and hashing leave
+    /// it out.
+    pub(crate) next_landings: u64,
+    /// The direction of the last jump of the capture in progress.
+    pub(crate) capture_direction: Option<(Direction, Direction)>,
+    /// The squares of the pieces captured so far in the current turn. They
+    /// are removed from the board once the turn is over.
+    pub(crate) captured: u64,
+    /// For each side, the king that made its last non-capturing moves and
+    /// how many of them it made in a row.
+    pub(crate) king_streaks: [Option<(Square, u8)>; 2],
+    /// The squares of the pieces that skipped a mandatory capture in the
+    /// last turn and may be huffed by the side to move.
+    pub(crate) huffable: u64,
+    pub(crate) turn: Color,
+    pub(crate) rules: &'static dyn RuleSet,
+    pub(crate) geometry: &'static Geometry,
+    pub(crate) capture_policy: CapturePolicy,
+    pub(crate) setup: Setup,
 }
 
 impl Board {
+    /// Creates a starting board for American checkers.
     pub fn new() -> Board {
-        let black_piece = Some(Piece {
-            color: Color::Black,
-            is_king: false,
-        });
-        let white_piece = Some(Piece {
-            color: Color::White,
-            is_king: false,
-        });
+        Board::for_rules(&rules::American)
+    }
+
+    /// Creates a starting board played by the given rules.
+    pub fn for_rules(rules: &'static dyn RuleSet) -> Board {
+        Board::for_setup(rules, Default::default())
+    }
+
+    /// Creates a starting board played by the given rules and set up as
+    /// described by `setup`.
+    pub fn for_setup(rules: &'static dyn RuleSet, setup: Setup) -> Board {
+        let mut board = Board::empty(rules, setup);
+        for index in 0..count_squares(rules) {
+            let (row, _) = get_position(rules, index);
+            let color = if rules.starting_rows(Color::Black).contains(&row) {
+                setup.top_color
+            } else if rules.starting_rows(Color::White).contains(&row) {
+                setup.top_color.opponent()
+            } else {
+                continue;
+            };
+            board.put(
+                index,
+                Some(Piece {
+                    color,
+                    is_king: false,
+                }),
+            );
+        }
+        board
+    }
+
+    /// Creates a board without pieces played by the given rules.
+    fn empty(rules: &'static dyn RuleSet, setup: Setup) -> Board {
         Board {
-            squares: [
-                [black_piece; 4],
-                [black_piece; 4],
-                [black_piece; 4],
-                [None; 4],
-                [None; 4],
-                [white_piece; 4],
-                [white_piece; 4],
-                [white_piece; 4],
-            ],
+            pieces: [0; 2],
+            kings: 0,
+            zobrist: zobrist::turn_key(setup.first_mover),
+            capturing: None,
+            next_landings: 0,
+            capture_direction: None,
+            captured: 0,
+            king_streaks: [None; 2],
+            huffable: 0,
+            turn: setup.first_mover,
+            rules,
+            geometry: Geometry::for_rules(rules),
+            capture_policy: CapturePolicy::Mandatory,
+            setup,
         }
     }
 
-    pub fn count_pieces(&self, color: Color) -> usize {
-        self.squares
-            .iter()
-            .flatten()
-            .filter(|p| p.map_or(false, |p| p.color == color))
-            .count()
-    }
-
-    pub fn get(&self, row: usize, col: usize) -> Option<GamePiece> {
-        match get_internal_col(row, col) {
-            Some(inner_col) => match &self.squares[row][inner_col] {
-                Some(piece) => Some(GamePiece {
-                    color: piece.color,
-                    is_king: piece.is_king,
-                    row,
-                    col,
-                }),
-                _ => None,
-            },
-            None => None,
+    /// Returns the same position with the given side to move.
+    pub fn with_turn(self, turn: Color) -> Board {
+        let mut board = self;
+        board.set_turn(turn);
+        board
+    }
+
+    /// Returns the same position played with the given capture policy.
+    pub fn with_capture_policy(self, capture_policy: CapturePolicy) -> Board {
+        Board {
+            capture_policy,
+            ..self
         }
     }
 
+    pub fn rules(&self) -> &'static dyn RuleSet {
+        self.rules
+    }
+
+    /// The number of rows and columns on the board.
+    pub fn size(&self) -> usize {
+        self.rules.size()
+    }
+
+    pub fn turn(&self) -> Color {
+        self.turn
+    }
+
+    /// A 64-bit hash of the pieces, the kings and the side to move. It is
+    /// the same in every build, so it can be stored.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.zobrist
+    }
+
+    pub fn setup(&self) -> Setup {
+        self.setup
+    }
+
+    pub fn capture_policy(&self) -> CapturePolicy {
+        self.capture_policy
+    }
+
+    pub fn count_pieces(&self, color: Color) -> usize {
+        self.pieces[color as usize].count_ones() as usize
+    }
+
+    /// Returns the piece on the square, if the square is on the board and
+    /// holds one.
+    pub fn get(&self, square: Square) -> Option<GamePiece> {
+        let index = get_index(self.rules, square.row, square.col)?;
+        self.piece_at(index).map(|piece| GamePiece {
+            color: piece.color,
+            is_king: piece.is_king,
+            row: square.row,
+            col: square.col,
+        })
+    }
+
     pub fn all_pieces(&self) -> impl Iterator<Item = GamePiece> + '_ {
         self.get_normalized_pieces()
     }
@@ -66,97 +181,581 @@ impl Board {
             .filter(move |piece| piece.color == color)
     }
 
-    pub fn moves_for(&self, row: usize, col: usize) -> Vec<Movement> {
-        rules::get_moves(&self, row, col)
+    pub fn moves_for(&self, square: Square) -> Vec<Movement> {
+        rules::get_moves(self, square)
     }
 
-    pub fn get_movable_pieces(&self, color: Color) -> impl Iterator<Item = GamePiece> + '_ {
-        let (forced, free): (Vec<_>, Vec<_>) = self
-            .get_normalized_pieces()
-            .filter(move |piece| piece.color == color)
-            .map(|piece| (piece, rules::get_moves(&self, piece.row, piece.col)))
-            .filter(|(_, movements)| !movements.is_empty())
-            .partition(|(_, m)| rules::has_forced_moves(m));
-        if !forced.is_empty() { forced } else { free }
-            .into_iter()
-            .map(|(p, _)| p)
+    pub fn sequences_for(&self, square: Square) -> Vec<MoveSequence> {
+        rules::get_sequences(self, square)
     }
 
-    pub fn make_move(
-        &self,
-        from_row: usize,
-        from_col: usize,
-        to_row: usize,
-        to_col: usize,
-    ) -> Result<Board, InvalidMove> {
-        let valid_move = rules::get_moves(&self, from_row, from_col)
+    /// Returns every complete legal turn for the given side, with the
+    /// square of the piece that makes it. While a capture is in progress
+    /// only its remaining part is returned.
+    pub fn legal_moves(&self, color: Color) -> Vec<LegalMove> {
+        rules::get_legal_sequences(self, color)
             .into_iter()
-            .find(|m| m.row == to_row && m.col == to_col);
-        match valid_move {
-            Some(move_) => {
-                let mut squares = self.squares.clone();
-                match move_.movement_type {
-                    MovementType::Forced => {
-                        let row = (from_row + to_row) / 2;
-                        let col =
-                            get_internal_col(row, (from_col + to_col) / 2).unwrap();
-                        squares[row][col] = None;
-                    }
-                    _ => {}
+            .map(|(piece, sequence)| LegalMove {
+                origin: piece.square(),
+                sequence,
+            })
+            .collect()
+    }
+
+    /// Returns the piece that has to continue its capture sequence before
+    /// the turn can end.
+    pub fn capturing_piece(&self) -> Option<GamePiece> {
+        self.capturing.and_then(|square| self.get(square))
+    }
+
+    /// Returns the pieces captured so far in the current turn. They stay on
+    /// the board until the turn is over.
+    pub fn captured_pieces(&self) -> impl Iterator<Item = GamePiece> + '_ {
+        self.get_normalized_pieces()
+            .filter(move |piece| self.is_captured(piece.row, piece.col))
+    }
+
+    /// Returns the king of the given side that made the last non-capturing
+    /// moves in a row, along with how many it made.
+    pub fn king_streak(&self, color: Color) -> Option<(Square, u8)> {
+        self.king_streaks[color as usize]
+    }
+
+    /// Returns the pieces of the side that just moved which skipped a
+    /// mandatory capture and may be huffed.
+    pub fn huffable_pieces(&self) -> impl Iterator<Item = GamePiece> + '_ {
+        indices(self.huffable).filter_map(move |index| self.piece_at_index(index))
+    }
+
+    /// Removes a piece that skipped a mandatory capture in the last turn.
+    ///
+    /// Huffing does not end the turn, the side to move still has to make
+    /// its move afterwards.
+    pub fn huff(&self, square: Square) -> Result<Board, InvalidMove> {
+        let index = self.check_square(square)?;
+        if matches!(self.piece_at(index), Some(piece) if piece.color == self.turn) {
+            return Err(InvalidMove::WrongTurn);
+        }
+        if self.huffable & (1u64 << index) == 0 {
+            return Err(InvalidMove::NotHuffable);
+        }
+        let mut board = *self;
+        board.put(index, None);
+        board.end_streaks(1u64 << index);
+        board.huffable = 0;
+        Ok(board)
+    }
+
+    pub fn get_movable_pieces(&self, color: Color) -> impl Iterator<Item = GamePiece> + '_ {
+        let mut pieces: Vec<GamePiece> = vec![];
+        for (piece, _) in rules::get_legal_sequences(self, color) {
+            if !pieces.contains(&piece) {
+                pieces.push(piece);
+            }
+        }
+        pieces.into_iter()
+    }
+
+    /// Returns the result of the game in this position.
+    ///
+    /// The side to move loses when it has no pieces or no legal moves left,
+    /// or wins if the rules are played to lose. Draws depend on the history
+    /// of the game, so a lone position never reports one.
+    pub fn result(&self) -> GameResult {
+        let has_moves = self.get_movable_pieces(self.turn).next().is_some();
+        if self.count_pieces(self.turn) == 0 || !has_moves {
+            if self.rules.is_losing_game() {
+                GameResult::win_for(self.turn)
+            } else {
+                GameResult::win_for(self.turn.opponent())
+            }
+        } else {
+            GameResult::Ongoing
+        }
+    }
+
+    pub fn make_move(&self, from_square: Square, to_square: Square) -> Result<Board, InvalidMove> {
+        let mut board = *self;
+        board.apply(from_square, to_square)?;
+        Ok(board)
+    }
+
+    /// Makes the move on this board in place. Returns a record of what the
+    /// move changed, which `undo` takes to restore the position.
+    pub fn apply(&mut self, from_square: Square, to_square: Square) -> Result<Undo, InvalidMove> {
+        let from = self.check_square(from_square)?;
+        let to = self.check_square(to_square)?;
+        let mut piece = match self.piece_at(from) {
+            Some(piece) if piece.color == self.turn => piece,
+            Some(_) => return Err(InvalidMove::WrongTurn),
+            None => return Err(InvalidMove::EmptyOrigin),
+        };
+        if matches!(self.capturing, Some(square) if square != from_square) {
+            return Err(InvalidMove::CaptureIncomplete);
+        }
+        if self.piece_at(to).is_some() {
+            return Err(InvalidMove::DestinationOccupied);
+        }
+        let forced = if self.capturing.is_some() {
+            // The previous jump already found where the capture may go on.
+            if self.next_landings & (1 << to) == 0 {
+                return Err(InvalidMove::CaptureIncomplete);
+            }
+            true
+        } else {
+            match self
+                .sequences_for(from_square)
+                .into_iter()
+                .find(|s| s.path[0] == to_square)
+            {
+                Some(move_) => move_.movement_type == MovementType::Forced,
+                None => return Err(self.explain_illegal_move()),
+            }
+        };
+        let skipped = if forced {
+            0
+        } else {
+            self.get_skipped_captures(from, to)
+        };
+        let mut undo = Undo {
+            from,
+            to,
+            piece,
+            removed: [0; 2],
+            removed_kings: 0,
+            capturing: self.capturing,
+            next_landings: self.next_landings,
+            capture_direction: self.capture_direction,
+            captured: self.captured,
+            king_streaks: self.king_streaks,
+            huffable: self.huffable,
+            turn: self.turn,
+        };
+        self.huffable = 0;
+        self.put(from, None);
+        if forced {
+            let captured = self.get_captured(from, to);
+            if self.rules.removes_captures_immediately() {
+                self.take_off(1 << captured, &mut undo);
+            } else {
+                self.captured |= 1 << captured;
+            }
+            self.capture_direction = Some(rules::get_direction(
+                from_square.row,
+                from_square.col,
+                to_square.row,
+                to_square.col,
+            ));
+            let next_landings = self.get_next_landings(piece, to_square);
+            if next_landings != 0 {
+                if self.rules.crowning() == Crowning::ContinuesAsKing {
+                    piece.is_king |= self.is_promotion_row(piece.color, to_square.row);
                 }
-                let col = get_internal_col(from_row, from_col).unwrap();
-                let mut piece = squares[from_row][col].take().unwrap();
-                let col = get_internal_col(to_row, to_col).unwrap();
-                match piece.color {
-                    Color::White => piece.is_king |= to_row == 0,
-                    Color::Black => piece.is_king |= to_row == 7,
+                self.put(to, Some(piece));
+                self.capturing = Some(to_square);
+                self.next_landings = next_landings;
+                return Ok(undo);
+            }
+        }
+        self.take_off(self.captured, &mut undo);
+        let counts = self.rules.max_king_moves().is_some();
+        let streak = &mut self.king_streaks[piece.color as usize];
+        *streak = if !counts || forced || !piece.is_king {
+            None
+        } else {
+            match *streak {
+                Some((king, moves)) if king == from_square => {
+                    Some((to_square, moves.saturating_add(1)))
                 }
-                squares[to_row][col] = Some(piece);
-                Ok(Board { squares })
+                _ => Some((to_square, 1)),
+            }
+        };
+        piece.is_king |= self.is_promotion_row(piece.color, to_square.row);
+        self.put(to, Some(piece));
+        self.huffable = skipped;
+        self.capturing = None;
+        self.next_landings = 0;
+        self.capture_direction = None;
+        self.captured = 0;
+        self.set_turn(piece.color.opponent());
+        Ok(undo)
+    }
+
+    /// Takes back a move made by `apply`. The undo records have to be given
+    /// back in the reverse order of the moves.
+    pub fn undo(&mut self, undo: Undo) {
+        self.put(undo.to, None);
+        self.put(undo.from, Some(undo.piece));
+        for &color in [Color::White, Color::Black].iter() {
+            for index in indices(undo.removed[color as usize]) {
+                let is_king = undo.removed_kings & (1 << index) != 0;
+                self.put(index, Some(Piece { color, is_king }));
             }
-            None => Err(InvalidMove),
         }
+        self.capturing = undo.capturing;
+        self.next_landings = undo.next_landings;
+        self.capture_direction = undo.capture_direction;
+        self.captured = undo.captured;
+        self.king_streaks = undo.king_streaks;
+        self.huffable = undo.huffable;
+        self.set_turn(undo.turn);
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.zobrist.hash(state);
+    }
+}
+
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        self.pieces == other.pieces
+            && self.kings == other.kings
+            && self.capturing == other.capturing
+            && self.capture_direction == other.capture_direction
+            && self.captured == other.captured
+            && self.king_streaks == other.king_streaks
+            && self.huffable == other.huffable
+            && self.turn == other.turn
+            && self.rules.name() == other.rules.name()
+            && self.capture_policy == other.capture_policy
+            && self.setup == other.setup
     }
 }
 
 // Not exported
 impl Board {
-    fn get_normalized_pieces(&self) -> impl Iterator<Item = GamePiece> + '_ {
-        self.squares
+    /// Rulesets describe the game with Black starting at the top of the
+    /// board. Returns the color that plays the same part as `color` does on
+    /// this board.
+    pub(crate) fn orient(&self, color: Color) -> Color {
+        match self.setup.top_color {
+            Color::Black => color,
+            Color::White => color.opponent(),
+        }
+    }
+
+    /// Returns the piece as the rules see it, see `orient`.
+    pub(crate) fn oriented(&self, piece: &GamePiece) -> GamePiece {
+        GamePiece {
+            color: self.orient(piece.color),
+            ..*piece
+        }
+    }
+
+    pub(crate) fn is_promotion_row(&self, color: Color, row: usize) -> bool {
+        self.rules.is_promotion_row(self.orient(color), row)
+    }
+
+    /// Returns the piece on the square with the given index.
+    pub(crate) fn piece_at(&self, index: usize) -> Option<Piece> {
+        let square = 1 << index;
+        let color = if self.pieces[Color::White as usize] & square != 0 {
+            Color::White
+        } else if self.pieces[Color::Black as usize] & square != 0 {
+            Color::Black
+        } else {
+            return None;
+        };
+        Some(Piece {
+            color,
+            is_king: self.kings & square != 0,
+        })
+    }
+
+    /// Returns the piece on the square with the given index along with
+    /// where it stands.
+    pub(crate) fn piece_at_index(&self, index: usize) -> Option<GamePiece> {
+        let (row, col) = self.geometry.position(index);
+        self.piece_at(index).map(|piece| GamePiece {
+            color: piece.color,
+            is_king: piece.is_king,
+            row,
+            col,
+        })
+    }
+
+    /// Places the piece on the square with the given index, or empties the
+    /// square.
+    pub(crate) fn put(&mut self, index: usize, piece: Option<Piece>) {
+        self.remove(1 << index);
+        if let Some(piece) = piece {
+            self.pieces[piece.color as usize] |= 1 << index;
+            if piece.is_king {
+                self.kings |= 1 << index;
+            }
+            self.zobrist ^= zobrist::piece_key(piece, index);
+        }
+    }
+
+    /// Empties every square in the set.
+    fn remove(&mut self, squares: u64) {
+        for index in indices(self.occupied() & squares) {
+            if let Some(piece) = self.piece_at(index) {
+                self.zobrist ^= zobrist::piece_key(piece, index);
+            }
+        }
+        self.pieces[0] &= !squares;
+        self.pieces[1] &= !squares;
+        self.kings &= !squares;
+    }
+
+    /// Removes the pieces on the squares in the set, noting them in `undo`.
+    fn take_off(&mut self, squares: u64, undo: &mut Undo) {
+        undo.removed[0] |= self.pieces[0] & squares;
+        undo.removed[1] |= self.pieces[1] & squares;
+        undo.removed_kings |= self.kings & squares;
+        self.remove(squares);
+        self.end_streaks(squares);
+    }
+
+    /// Forgets the quiet moves of kings that are no longer on the board.
+    fn end_streaks(&mut self, squares: u64) {
+        for color in [Color::White, Color::Black] {
+            if let Some((king, _)) = self.king_streaks[color as usize] {
+                if squares & (1u64 << self.index_of(king)) != 0 {
+                    self.king_streaks[color as usize] = None;
+                }
+            }
+        }
+    }
+
+    fn set_turn(&mut self, turn: Color) {
+        self.zobrist ^= zobrist::turn_key(self.turn) ^ zobrist::turn_key(turn);
+        self.turn = turn;
+    }
+
+    /// Every square holding a piece.
+    pub(crate) fn occupied(&self) -> u64 {
+        self.pieces[0] | self.pieces[1]
+    }
+
+    pub(crate) fn is_captured(&self, row: usize, col: usize) -> bool {
+        match get_index(self.rules, row, col) {
+            Some(index) => self.captured & (1u64 << index) != 0,
+            None => false,
+        }
+    }
+
+    /// Returns the index of a square handed out by the crate.
+    pub(crate) fn index_of(&self, square: Square) -> usize {
+        get_index(self.rules, square.row, square.col).expect("squares are on the board")
+    }
+
+    /// Checks that the square is a playable one and returns its index.
+    fn check_square(&self, square: Square) -> Result<usize, InvalidMove> {
+        Square::new(self.rules, square.row, square.col)?;
+        Ok(get_index(self.rules, square.row, square.col).unwrap())
+    }
+
+    /// Tells why a piece of the side to move can not make a move that is
+    /// not among its legal ones.
+    fn explain_illegal_move(&self) -> InvalidMove {
+        let must_capture = rules::get_legal_sequences(self, self.turn)
+            .iter()
+            .any(|(_, sequence)| sequence.movement_type == MovementType::Forced);
+        if self.capturing.is_some() {
+            InvalidMove::CaptureIncomplete
+        } else if must_capture && rules::is_capture_mandatory(self) {
+            InvalidMove::CaptureRequired
+        } else {
+            InvalidMove::IllegalMove
+        }
+    }
+
+    /// Returns the squares the piece that just jumped to `square` may land
+    /// on with its next jump, none if the capture is over. The jump has to
+    /// be recorded on the board already, apart from the piece itself.
+    fn get_next_landings(&self, mut piece: Piece, square: Square) -> u64 {
+        if !piece.is_king && self.is_promotion_row(piece.color, square.row) {
+            match self.rules.crowning() {
+                Crowning::EndsTurn => return 0,
+                Crowning::ContinuesAsKing => piece.is_king = true,
+                Crowning::PassesThrough => {}
+            }
+        }
+        let mut board = *self;
+        board.put(self.index_of(square), Some(piece));
+        board.capturing = Some(square);
+        board.get_landings()
+    }
+
+    /// Returns the squares the legal turns of the side to move make their
+    /// first step to.
+    pub(crate) fn get_landings(&self) -> u64 {
+        rules::get_legal_sequences(self, self.turn)
             .iter()
-            .enumerate()
-            .map(|(row, pieces)| {
-                pieces
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, piece)| piece.is_some())
-                    .map(move |(col, piece)| {
-                        let piece = piece.as_ref().unwrap();
-                        GamePiece {
-                            color: piece.color,
-                            is_king: piece.is_king,
-                            row,
-                            col: get_external_col(row, col),
-                        }
-                    })
+            .fold(0, |landings, (_, sequence)| {
+                landings | 1 << self.index_of(sequence.path[0])
             })
-            .flatten()
+    }
+
+    /// Returns the squares of the pieces that could have captured when the
+    /// side to move makes the free move between the two indices, as they
+    /// stand after the move. Only huffing keeps track of them.
+    fn get_skipped_captures(&self, from: usize, to: usize) -> u64 {
+        if self.capture_policy != CapturePolicy::Huffing || !self.rules.is_capture_mandatory() {
+            return 0;
+        }
+        let mandatory = self.with_capture_policy(CapturePolicy::Mandatory);
+        let mut skipped = 0;
+        for (piece, sequence) in rules::get_legal_sequences(&mandatory, self.turn) {
+            if sequence.movement_type == MovementType::Forced {
+                if let Some(index) = get_index(self.rules, piece.row, piece.col) {
+                    skipped |= 1u64 << if index == from { to } else { index };
+                }
+            }
+        }
+        skipped
+    }
+
+    /// Finds the piece jumped over when moving between the squares with
+    /// the two indices, and returns the index of its square.
+    fn get_captured(&self, from: usize, to: usize) -> usize {
+        let (from_row, from_col) = self.geometry.position(from);
+        let (to_row, to_col) = self.geometry.position(to);
+        let direction = rules::get_direction(from_row, from_col, to_row, to_col);
+        let mut index = from;
+        loop {
+            index = match self.geometry.step(index, direction) {
+                Some(index) => index,
+                None => return to,
+            };
+            if (self.occupied() & !self.captured) & (1 << index) != 0 {
+                return index;
+            }
+        }
+    }
+
+    fn get_normalized_pieces(&self) -> impl Iterator<Item = GamePiece> + '_ {
+        indices(self.occupied()).filter_map(move |index| self.piece_at_index(index))
     }
 }
 
-fn get_internal_col(row: usize, col: usize) -> Option<usize> {
-    if row >= 8 || row >= 8 {
+/// Maps a playable square of a board played by the given rules to its
+/// index in `Board::squares`.
+pub(crate) fn get_index(rules: &dyn RuleSet, row: usize, col: usize) -> Option<usize> {
+    let size = rules.size();
+    if row >= size || col >= size {
         return None;
     }
-    match (row % 2, col % 2) {
-        (1, 0) => Some(col / 2),
-        (0, 1) => Some((col - 1) / 2),
+    match rules.layout() {
+        Layout::Full => Some(row * size + col),
+        layout if col % 2 == get_first_col(layout, row) => Some(row * size / 2 + col / 2),
         _ => None,
     }
 }
 
-fn get_external_col(row: usize, col: usize) -> usize {
-    col * 2 + (row + 1) % 2
+/// The inverse of `get_index`.
+pub(crate) fn get_position(rules: &dyn RuleSet, index: usize) -> (usize, usize) {
+    let size = rules.size();
+    match rules.layout() {
+        Layout::Full => (index / size, index % size),
+        layout => {
+            let per_row = size / 2;
+            let row = index / per_row;
+            (row, (index % per_row) * 2 + get_first_col(layout, row))
+        }
+    }
+}
+
+/// The number of playable squares of a board played by the given rules.
+pub(crate) fn count_squares(rules: &dyn RuleSet) -> usize {
+    let size = rules.size();
+    match rules.layout() {
+        Layout::Full => size * size,
+        _ => size * size / 2,
+    }
+}
+
+/// Returns the column of the first playable square in the given row.
+fn get_first_col(layout: Layout, row: usize) -> usize {
+    match layout {
+        Layout::Dark => (row + 1) % 2,
+        Layout::Flipped => row % 2,
+        Layout::Full => 0,
+    }
+}
+
+/// Which squares of the board the game is played on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    /// The dark squares, with a light square in the top left corner.
+    Dark,
+    /// The dark squares of a board turned a quarter, with a dark square in
+    /// the top left corner.
+    Flipped,
+    /// Every square of the board.
+    Full,
+}
+
+/// What a move made in place changed, so that it can be taken back.
+#[derive(Debug, Clone, Copy)]
+pub struct Undo {
+    from: usize,
+    to: usize,
+    /// The moving piece as it was before the move, so before any crowning.
+    piece: Piece,
+    /// The pieces the move took off the board, by color.
+    removed: [u64; 2],
+    removed_kings: u64,
+    capturing: Option<Square>,
+    next_landings: u64,
+    capture_direction: Option<(Direction, Direction)>,
+    captured: u64,
+    king_streaks: [Option<(Square, u8)>; 2],
+    huffable: u64,
+    turn: Color,
+}
+
+/// How the pieces are placed before the first move and who makes it.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Setup {
+    /// The side that makes the first move.
+    pub first_mover: Color,
+    /// The side whose pieces start on the top rows, on squares 1 to 12 of
+    /// an American board.
+    pub top_color: Color,
+}
+
+#[wasm_bindgen]
+impl Setup {
+    #[wasm_bindgen(constructor)]
+    pub fn new(first_mover: Color, top_color: Color) -> Setup {
+        Setup {
+            first_mover,
+            top_color,
+        }
+    }
+
+    /// The official convention: Black starts on squares 1 to 12 and moves
+    /// first.
+    pub fn official() -> Setup {
+        Setup {
+            first_mover: Color::Black,
+            top_color: Color::Black,
+        }
+    }
+}
+
+/// Black starts at the top but White moves first.
+impl Default for Setup {
+    fn default() -> Self {
+        Setup {
+            first_mover: Color::White,
+            top_color: Color::Black,
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -169,18 +768,23 @@ pub struct GamePiece {
 }
 
 impl GamePiece {
+    /// The square the piece stands on.
+    pub fn square(&self) -> Square {
+        Square::at(self.row, self.col)
+    }
+
     pub(crate) fn into_vec(self) -> Vec<u8> {
         self.into()
     }
 }
 
-impl Into<Vec<u8>> for GamePiece {
-    fn into(self) -> Vec<u8> {
+impl From<GamePiece> for Vec<u8> {
+    fn from(piece: GamePiece) -> Vec<u8> {
         vec![
-            self.color as u8,
-            self.is_king as u8,
-            u8::try_from(self.row).unwrap(),
-            u8::try_from(self.col).unwrap(),
+            piece.color as u8,
+            piece.is_king as u8,
+            to_byte(piece.row),
+            to_byte(piece.col),
         ]
     }
 }
@@ -199,8 +803,103 @@ pub enum Color {
     Black,
 }
 
-#[derive(Debug)]
-pub struct InvalidMove;
+impl Color {
+    pub fn opponent(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameResult {
+    Ongoing,
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+impl GameResult {
+    pub fn win_for(color: Color) -> GameResult {
+        match color {
+            Color::White => GameResult::WhiteWins,
+            Color::Black => GameResult::BlackWins,
+        }
+    }
+
+    pub fn is_over(self) -> bool {
+        self != GameResult::Ongoing
+    }
+}
+
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrawReason {
+    /// The same position has occurred too many times.
+    Repetition,
+    /// Too many moves have been made without a capture or a man moving.
+    MoveLimit,
+}
+
+/// Why a move was rejected.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InvalidMove {
+    /// A square lies outside the board.
+    OffBoard,
+    /// A square is not played on.
+    LightSquare,
+    /// There is no piece to move.
+    EmptyOrigin,
+    /// The piece belongs to the opponent of the side the move was made for.
+    OpponentsPiece,
+    /// The piece is used on the wrong turn: moved while its side is not to
+    /// move, or huffed while it is.
+    WrongTurn,
+    /// The destination already holds a piece.
+    DestinationOccupied,
+    /// Another piece has a capture that has to be made.
+    CaptureRequired,
+    /// A capture is in progress and the capturing piece has to go on.
+    CaptureIncomplete,
+    /// The piece can not move to the destination.
+    IllegalMove,
+    /// The piece did not skip a mandatory capture in the last turn.
+    NotHuffable,
+    /// The game is already over.
+    GameOver,
+}
+
+impl InvalidMove {
+    pub fn message(self) -> &'static str {
+        match self {
+            InvalidMove::OffBoard => "The square is not on the board",
+            InvalidMove::LightSquare => "The game is not played on that square",
+            InvalidMove::EmptyOrigin => "There is no piece to move",
+            InvalidMove::OpponentsPiece => "That piece belongs to the opponent",
+            InvalidMove::WrongTurn => "It is not that side's turn",
+            InvalidMove::DestinationOccupied => "The destination is occupied",
+            InvalidMove::CaptureRequired => "A capture has to be made",
+            InvalidMove::CaptureIncomplete => "The capturing piece has to keep capturing",
+            InvalidMove::IllegalMove => "The piece can not move there",
+            InvalidMove::NotHuffable => "That piece can not be huffed",
+            InvalidMove::GameOver => "The game is over",
+        }
+    }
+}
+
+impl fmt::Display for InvalidMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for InvalidMove {}
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,84 @@
+use super::{count_squares, get_index, get_position, InvalidMove};
+use crate::rules::RuleSet;
+
+/// A playable square of a board.
+///
+/// Squares handed out by the crate always lie on the board they belong to.
+/// Ones made from outside are checked against the rules of the board they
+/// are meant for, so they can be made from a row and a column, from the
+/// standard numbering of the playable squares or from an algebraic name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Square {
+    pub(crate) row: usize,
+    pub(crate) col: usize,
+}
+
+impl Square {
+    /// The square at the given row and column, counted from the top left
+    /// corner of the board.
+    pub fn new(rules: &dyn RuleSet, row: usize, col: usize) -> Result<Square, InvalidMove> {
+        if row >= rules.size() || col >= rules.size() {
+            return Err(InvalidMove::OffBoard);
+        }
+        match get_index(rules, row, col) {
+            Some(_) => Ok(Square { row, col }),
+            None => Err(InvalidMove::LightSquare),
+        }
+    }
+
+    /// The square with the given number. Playable squares are numbered from
+    /// 1, row by row from the top left corner, so 1 to 32 on an American
+    /// board.
+    pub fn from_number(rules: &dyn RuleSet, number: usize) -> Result<Square, InvalidMove> {
+        if number == 0 || number > count_squares(rules) {
+            return Err(InvalidMove::OffBoard);
+        }
+        let (row, col) = get_position(rules, number - 1);
+        Ok(Square { row, col })
+    }
+
+    /// The square with the given algebraic name, such as `c3`. Files are
+    /// lettered from the left and ranks numbered from the bottom.
+    pub fn from_name(rules: &dyn RuleSet, name: &str) -> Result<Square, InvalidMove> {
+        let mut chars = name.chars();
+        let file = match chars.next() {
+            Some(file @ 'a'..='z') => file as usize - 'a' as usize,
+            _ => return Err(InvalidMove::OffBoard),
+        };
+        let rank: usize = match chars.as_str().parse() {
+            Ok(rank) if rank >= 1 && rank <= rules.size() => rank,
+            _ => return Err(InvalidMove::OffBoard),
+        };
+        Square::new(rules, rules.size() - rank, file)
+    }
+
+    /// Makes a square without checking it, for coordinates the crate works
+    /// out itself.
+    pub(crate) fn at(row: usize, col: usize) -> Square {
+        Square { row, col }
+    }
+
+    pub fn row(self) -> usize {
+        self.row
+    }
+
+    pub fn col(self) -> usize {
+        self.col
+    }
+
+    /// The number of the square on a board played by the given rules, if
+    /// the square is playable there.
+    pub fn number(self, rules: &dyn RuleSet) -> Option<usize> {
+        get_index(rules, self.row, self.col).map(|index| index + 1)
+    }
+
+    /// The algebraic name of the square on a board played by the given
+    /// rules, if the square is on that board.
+    pub fn name(self, rules: &dyn RuleSet) -> Option<String> {
+        if self.row >= rules.size() || self.col >= rules.size() {
+            return None;
+        }
+        let file = (b'a' + self.col as u8) as char;
+        Some(format!("{}{}", file, rules.size() - self.row))
+    }
+}
@@ -0,0 +1,195 @@
+use wasm_bindgen::prelude::*;
+
+use super::{get_index, get_position, indices, Board, Color, Piece, Square};
+use crate::rules::{Direction, RuleSet};
+
+/// A way of mapping a board onto itself that keeps the game the same.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    Identity,
+    /// Mirrors the board left to right. Only boards whose playable squares
+    /// mirror onto playable squares allow it, so not boards of dark squares
+    /// with an even size.
+    Mirror,
+    /// Turns the board half way round and swaps the colors, so that each
+    /// side takes the place of the other.
+    Rotate,
+    /// Mirrors the board and then rotates it.
+    MirrorRotate,
+}
+
+/// Every symmetry, the identity first.
+pub const SYMMETRIES: [Symmetry; 4] = [
+    Symmetry::Identity,
+    Symmetry::Mirror,
+    Symmetry::Rotate,
+    Symmetry::MirrorRotate,
+];
+
+impl Symmetry {
+    /// Whether boards played by the given rules can be transformed this way.
+    pub fn applies_to(self, rules: &dyn RuleSet) -> bool {
+        if !self.mirrors() {
+            return true;
+        }
+        let (row, col) = get_position(rules, 0);
+        get_index(rules, row, rules.size() - 1 - col).is_some()
+    }
+
+    /// Every symmetry undoes itself.
+    pub fn inverse(self) -> Symmetry {
+        self
+    }
+
+    pub fn swaps_colors(self) -> bool {
+        matches!(self, Symmetry::Rotate | Symmetry::MirrorRotate)
+    }
+
+    fn mirrors(self) -> bool {
+        matches!(self, Symmetry::Mirror | Symmetry::MirrorRotate)
+    }
+
+    /// Where the square ends up on a board played by the given rules.
+    pub fn square(self, rules: &dyn RuleSet, square: Square) -> Square {
+        let last = rules.size() - 1;
+        let col = if self.mirrors() {
+            last - square.col
+        } else {
+            square.col
+        };
+        if self.swaps_colors() {
+            Square::at(last - square.row, last - col)
+        } else {
+            Square::at(square.row, col)
+        }
+    }
+
+    pub fn color(self, color: Color) -> Color {
+        if self.swaps_colors() {
+            color.opponent()
+        } else {
+            color
+        }
+    }
+
+    /// Where a step in the given direction points after the transformation.
+    pub fn direction(self, (row, col): (Direction, Direction)) -> (Direction, Direction) {
+        let col = if self.mirrors() { col.opposite() } else { col };
+        if self.swaps_colors() {
+            (row.opposite(), col.opposite())
+        } else {
+            (row, col)
+        }
+    }
+}
+
+impl Board {
+    /// Returns the position transformed by the symmetry, or `None` if the
+    /// board does not allow it.
+    pub fn transformed(&self, symmetry: Symmetry) -> Option<Board> {
+        if symmetry.applies_to(self.rules) {
+            Some(self.transform(symmetry))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the position mirrored left to right, if the board allows it.
+    ///
+    /// Boards of dark squares with an even size, such as the American and
+    /// the International ones, never do: mirroring moves every dark square
+    /// onto a light one, so this returns `None` for them.
+    pub fn mirrored(&self) -> Option<Board> {
+        self.transformed(Symmetry::Mirror)
+    }
+
+    /// Returns the position turned half way round with the colors swapped.
+    pub fn rotated(&self) -> Board {
+        self.transform(Symmetry::Rotate)
+    }
+
+    /// Returns the representative of the positions the symmetries of the
+    /// board lead to, along with the symmetry that turns this position into
+    /// it. Every position of the class has the same representative.
+    ///
+    /// Only the symmetries the board allows are tried, so on boards of dark
+    /// squares with an even size the class is just the position and its
+    /// rotation, see `mirrored`.
+    pub fn canonical(&self) -> (Board, Symmetry) {
+        SYMMETRIES
+            .iter()
+            .filter(|symmetry| symmetry.applies_to(self.rules))
+            .map(|&symmetry| (self.transform(symmetry), symmetry))
+            .min_by_key(|(board, _)| board.sort_key())
+            .unwrap_or((*self, Symmetry::Identity))
+    }
+
+    fn transform(&self, symmetry: Symmetry) -> Board {
+        let rules = self.rules;
+        let square = |square: Square| symmetry.square(rules, square);
+        let squares = |squares: u64| {
+            indices(squares)
+                .map(|index| {
+                    let (row, col) = self.geometry.position(index);
+                    1 << self.index_of(square(Square::at(row, col)))
+                })
+                .fold(0, |result, square| result | square)
+        };
+        let mut board = Board::empty(rules, self.setup);
+        for piece in self.all_pieces() {
+            let piece_square = square(piece.square());
+            board.put(
+                board.index_of(piece_square),
+                Some(Piece {
+                    color: symmetry.color(piece.color),
+                    is_king: piece.is_king,
+                }),
+            );
+        }
+        board.set_turn(symmetry.color(self.turn));
+        board.capturing = self.capturing.map(square);
+        board.next_landings = squares(self.next_landings);
+        board.capture_direction = self.capture_direction.map(|d| symmetry.direction(d));
+        board.captured = squares(self.captured);
+        board.huffable = squares(self.huffable);
+        for &color in [Color::White, Color::Black].iter() {
+            board.king_streaks[symmetry.color(color) as usize] =
+                self.king_streaks[color as usize].map(|(king, moves)| (square(king), moves));
+        }
+        board.capture_policy = self.capture_policy;
+        board
+    }
+
+    /// Orders positions for `canonical`. It takes in every field equality
+    /// compares, apart from the rules, the capture policy and the setup,
+    /// which the symmetries keep.
+    #[allow(clippy::type_complexity)]
+    fn sort_key(
+        &self,
+    ) -> (
+        u8,
+        [u64; 2],
+        u64,
+        u64,
+        u64,
+        Option<usize>,
+        Option<(u8, u8)>,
+        [Option<(usize, u8)>; 2],
+    ) {
+        let king_streak = |color: Color| {
+            self.king_streaks[color as usize].map(|(king, moves)| (self.index_of(king), moves))
+        };
+        (
+            self.turn as u8,
+            self.pieces,
+            self.kings,
+            self.captured,
+            self.huffable,
+            self.capturing.map(|square| self.index_of(square)),
+            self.capture_direction
+                .map(|(row, col)| (row as u8, col as u8)),
+            [king_streak(Color::White), king_streak(Color::Black)],
+        )
+    }
+}
@@ -1,5 +1,5 @@
 use super::*;
-use crate::rules::MovementType;
+use crate::rules::{CapturePolicy, LegalMove, MoveSequence, MovementType};
 use crate::utils;
 
 macro_rules! game_piece(
@@ -36,8 +36,8 @@ fn constructs_a_starting_board_with_12_pieces() {
     ];
     for position in black_pieces {
         let piece = board
-            .get(position.0, position.1)
-            .expect(&format!("Piece not found at {:?}", position));
+            .get(Square::at(position.0, position.1))
+            .unwrap_or_else(|| panic!("Piece not found at {:?}", position));
         assert_eq!(piece.color, Color::Black);
     }
 
@@ -57,8 +57,8 @@ fn constructs_a_starting_board_with_12_pieces() {
     ];
     for (row, col) in white_pieces {
         let piece = board
-            .get(row, col)
-            .expect(&format!("Piece not found at {:?}", (row, col)));
+            .get(Square::at(row, col))
+            .unwrap_or_else(|| panic!("Piece not found at {:?}", (row, col)));
         assert_eq!(piece.color, Color::White);
     }
 }
@@ -102,10 +102,19 @@ fn returns_none_for_white_squares() {
         (7, 7), // 8th row
     ];
     for position in white_squares {
-        assert!(board.get(position.0, position.1).is_none())
+        assert!(board.get(Square::at(position.0, position.1)).is_none())
     }
 }
 
+#[test]
+fn returns_none_for_squares_off_the_board() {
+    let rules = &rules::American;
+
+    assert_eq!(get_index(rules, 0, 9), None);
+    assert_eq!(get_index(rules, 9, 0), None);
+    assert_eq!(Square::new(rules, 0, 9), Err(InvalidMove::OffBoard));
+}
+
 #[test]
 fn allows_iterating_over_pieces() {
     let board = Board::new();
@@ -137,7 +146,7 @@ fn allows_iterating_over_pieces() {
         game_piece!(Color::White, false, 7, 6), // 1st white row
     ];
 
-    for (actual, expected) in board.all_pieces().into_iter().zip(expected) {
+    for (actual, expected) in board.all_pieces().zip(expected) {
         assert_eq!(actual, expected);
     }
 }
@@ -194,7 +203,7 @@ fn allows_iterating_over_black_pieces() {
 fn produces_valid_moves_for_a_starting_board() {
     let board = Board::new();
 
-    let moves = board.moves_for(5, 2);
+    let moves = board.moves_for(Square::at(5, 2));
 
     assert_eq!(moves.len(), 2);
     assert_eq!(
@@ -231,9 +240,9 @@ fn produces_valid_movable_pieces_for_a_staring_board() {
 #[test]
 fn produces_only_forced_movable_pieces_if_some_are_available() {
     let board = Board::new()
-        .make_move(5, 0, 4, 1)
+        .make_move(Square::at(5, 0), Square::at(4, 1))
         .unwrap()
-        .make_move(2, 3, 3, 2)
+        .make_move(Square::at(2, 3), Square::at(3, 2))
         .unwrap();
 
     let pieces: Vec<_> = board.get_movable_pieces(Color::White).collect();
@@ -246,36 +255,50 @@ fn produces_only_forced_movable_pieces_if_some_are_available() {
 fn attempting_to_move_empty_square_fails() {
     let board = Board::new();
 
-    let result = board.make_move(4, 1, 3, 2);
-    assert!(result.is_err());
+    let result = board.make_move(Square::at(4, 1), Square::at(3, 2));
+    assert_eq!(result.err(), Some(InvalidMove::EmptyOrigin));
 }
 
 #[test]
 fn attempting_to_move_white_square_fails() {
     let board = Board::new();
 
-    let result = board.make_move(3, 1, 2, 2);
-    assert!(result.is_err());
+    let result = board.make_move(Square::at(3, 1), Square::at(2, 2));
+    assert_eq!(result.err(), Some(InvalidMove::LightSquare));
 }
 
 #[test]
 fn attempting_an_invalid_move_fails() {
     let board = Board::new();
 
-    let result = board.make_move(5, 2, 2, 1);
-    assert!(result.is_err());
+    assert_eq!(
+        board.make_move(Square::at(5, 2), Square::at(2, 1)).err(),
+        Some(InvalidMove::DestinationOccupied)
+    );
+    assert_eq!(
+        board.make_move(Square::at(5, 2), Square::at(3, 2)).err(),
+        Some(InvalidMove::IllegalMove)
+    );
+}
+
+#[test]
+fn attempting_to_move_off_the_board_fails() {
+    let board = Board::new();
+
+    let result = board.make_move(Square::at(7, 6), Square::at(8, 7));
+    assert_eq!(result.err(), Some(InvalidMove::OffBoard));
 }
 
 #[test]
 fn move_normally() {
     let board = Board::new();
 
-    let result = board.make_move(5, 2, 4, 1);
+    let result = board.make_move(Square::at(5, 2), Square::at(4, 1));
     assert!(result.is_ok());
     let board = result.unwrap();
 
-    assert!(board.get(5, 2).is_none());
-    assert!(board.get(4, 1).is_some());
+    assert!(board.get(Square::at(5, 2)).is_none());
+    assert!(board.get(Square::at(4, 1)).is_some());
     assert_eq!(board.count_pieces(Color::White), 12);
     assert_eq!(board.count_pieces(Color::Black), 12);
 }
@@ -283,29 +306,35 @@ fn move_normally() {
 #[test]
 fn move_by_taking() {
     let mut board = Board::new();
-    board.squares[5][0] = None;
-    board.squares[4][0] = Some(Piece {
-        color: Color::White,
-        is_king: false,
-    });
-    board.squares[2][1] = None;
-    board.squares[3][1] = Some(Piece {
-        color: Color::Black,
-        is_king: false,
-    });
-
-    assert!(board.get(5, 0).is_none());
-    assert!(board.get(4, 1).is_some());
-    assert!(board.get(2, 3).is_none());
-    assert!(board.get(3, 2).is_some());
-
-    let result = board.make_move(4, 1, 2, 3);
+    board.put(20, None);
+    board.put(
+        16,
+        Some(Piece {
+            color: Color::White,
+            is_king: false,
+        }),
+    );
+    board.put(9, None);
+    board.put(
+        13,
+        Some(Piece {
+            color: Color::Black,
+            is_king: false,
+        }),
+    );
+
+    assert!(board.get(Square::at(5, 0)).is_none());
+    assert!(board.get(Square::at(4, 1)).is_some());
+    assert!(board.get(Square::at(2, 3)).is_none());
+    assert!(board.get(Square::at(3, 2)).is_some());
+
+    let result = board.make_move(Square::at(4, 1), Square::at(2, 3));
     assert!(result.is_ok());
     let board = result.unwrap();
 
-    assert!(board.get(2, 3).is_some());
-    assert!(board.get(3, 2).is_none());
-    assert!(board.get(4, 1).is_none());
+    assert!(board.get(Square::at(2, 3)).is_some());
+    assert!(board.get(Square::at(3, 2)).is_none());
+    assert!(board.get(Square::at(4, 1)).is_none());
     assert_eq!(board.count_pieces(Color::White), 12);
     assert_eq!(board.count_pieces(Color::Black), 11);
 }
@@ -323,19 +352,19 @@ fn moving_to_last_row_makes_king() {
         "* ' * ' * ' * '",
     ]);
 
-    assert!(board.get(0, 1).is_none());
-    assert!(board.get(1, 0).is_some());
+    assert!(board.get(Square::at(0, 1)).is_none());
+    assert!(board.get(Square::at(1, 0)).is_some());
 
-    let result = board.make_move(1, 0, 0, 1);
+    let result = board.make_move(Square::at(1, 0), Square::at(0, 1));
     assert!(result.is_ok());
     let board = result.unwrap();
 
-    assert!(board.get(0, 1).is_some());
-    assert!(board.get(1, 0).is_none());
+    assert!(board.get(Square::at(0, 1)).is_some());
+    assert!(board.get(Square::at(1, 0)).is_none());
     assert_eq!(board.count_pieces(Color::White), 1);
     assert_eq!(board.count_pieces(Color::Black), 1);
 
-    let piece = board.get(0, 1).unwrap();
+    let piece = board.get(Square::at(0, 1)).unwrap();
     assert!(piece.is_king);
 }
 
@@ -352,18 +381,1084 @@ fn moving_from_last_row_remains_king() {
         "* ' * ' * ' * '",
     ]);
 
-    assert!(board.get(0, 1).is_some());
-    assert!(board.get(1, 0).is_none());
+    assert!(board.get(Square::at(0, 1)).is_some());
+    assert!(board.get(Square::at(1, 0)).is_none());
 
-    let result = board.make_move(0, 1, 1, 0);
+    let result = board.make_move(Square::at(0, 1), Square::at(1, 0));
     assert!(result.is_ok());
     let board = result.unwrap();
 
-    assert!(board.get(0, 1).is_none());
-    assert!(board.get(1, 0).is_some());
+    assert!(board.get(Square::at(0, 1)).is_none());
+    assert!(board.get(Square::at(1, 0)).is_some());
     assert_eq!(board.count_pieces(Color::White), 1);
     assert_eq!(board.count_pieces(Color::Black), 1);
 
-    let piece = board.get(1, 0).unwrap();
+    let piece = board.get(Square::at(1, 0)).unwrap();
     assert!(piece.is_king);
 }
+
+#[test]
+fn capture_sequence_must_be_continued_by_the_same_piece() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' w ' * ' w '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let board = board.make_move(Square::at(5, 2), Square::at(3, 4)).unwrap();
+
+    assert_eq!(
+        board.capturing_piece(),
+        Some(game_piece!(Color::White, false, 3, 4))
+    );
+    let pieces: Vec<_> = board.get_movable_pieces(Color::White).collect();
+    assert_eq!(pieces, vec![game_piece!(Color::White, false, 3, 4)]);
+    assert!(board.moves_for(Square::at(5, 6)).is_empty());
+    assert_eq!(
+        board.make_move(Square::at(5, 6), Square::at(4, 5)).err(),
+        Some(InvalidMove::CaptureIncomplete)
+    );
+    assert_eq!(
+        board.make_move(Square::at(3, 4), Square::at(2, 5)).err(),
+        Some(InvalidMove::CaptureIncomplete)
+    );
+
+    let board = board.make_move(Square::at(3, 4), Square::at(1, 2)).unwrap();
+
+    assert_eq!(board.capturing_piece(), None);
+    assert_eq!(board.count_pieces(Color::Black), 0);
+}
+
+#[test]
+fn capture_sequence_ends_when_the_piece_is_crowned() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' b ' b ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let board = board.make_move(Square::at(2, 1), Square::at(0, 3)).unwrap();
+
+    assert!(board.get(Square::at(0, 3)).unwrap().is_king);
+    assert_eq!(board.capturing_piece(), None);
+    assert_eq!(board.count_pieces(Color::Black), 1);
+}
+
+#[test]
+fn capture_in_progress_goes_on_along_the_longest_sequence() {
+    let board = utils::make_board_for(
+        &rules::Brazilian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' b ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' b ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' w ' * ' * '",
+        ],
+    );
+
+    let board = board.make_move(Square::at(7, 2), Square::at(5, 4)).unwrap();
+
+    assert_eq!(
+        board.make_move(Square::at(5, 4), Square::at(3, 2)).err(),
+        Some(InvalidMove::CaptureIncomplete)
+    );
+    let board = board.make_move(Square::at(5, 4), Square::at(3, 6)).unwrap();
+    assert_eq!(
+        board.capturing_piece(),
+        Some(game_piece!(Color::White, false, 3, 6))
+    );
+
+    let board = board.make_move(Square::at(3, 6), Square::at(1, 4)).unwrap();
+
+    assert_eq!(board.capturing_piece(), None);
+    assert_eq!(board.count_pieces(Color::Black), 1);
+}
+
+#[test]
+fn white_moves_first() {
+    let board = Board::new();
+
+    assert_eq!(board.turn(), Color::White);
+    assert_eq!(
+        board.make_move(Square::at(2, 1), Square::at(3, 0)).err(),
+        Some(InvalidMove::WrongTurn)
+    );
+}
+
+#[test]
+fn turn_passes_after_a_finished_move() {
+    let board = Board::new()
+        .make_move(Square::at(5, 2), Square::at(4, 1))
+        .unwrap();
+
+    assert_eq!(board.turn(), Color::Black);
+    assert!(board.make_move(Square::at(5, 4), Square::at(4, 3)).is_err());
+    assert!(board.make_move(Square::at(2, 1), Square::at(3, 0)).is_ok());
+}
+
+#[test]
+fn turn_is_kept_during_a_capture_sequence() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' w ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let board = board.make_move(Square::at(5, 2), Square::at(3, 4)).unwrap();
+    assert_eq!(board.turn(), Color::White);
+
+    let board = board.make_move(Square::at(3, 4), Square::at(1, 2)).unwrap();
+    assert_eq!(board.turn(), Color::Black);
+}
+
+#[test]
+fn starting_board_is_ongoing() {
+    assert_eq!(Board::new().result(), GameResult::Ongoing);
+}
+
+#[test]
+fn side_without_pieces_loses() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    assert_eq!(board.result(), GameResult::BlackWins);
+}
+
+#[test]
+fn side_without_moves_loses() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' b ' * ' * ' *",
+        "w ' * ' * ' * '",
+    ]);
+
+    assert_eq!(board.result(), GameResult::BlackWins);
+    assert_eq!(board.with_turn(Color::Black).result(), GameResult::Ongoing);
+}
+
+#[test]
+fn giveaway_side_without_pieces_or_moves_wins() {
+    let board = utils::make_board_for(
+        &rules::Giveaway,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' b ' * ' * '",
+            "' b ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    );
+    assert_eq!(board.result(), GameResult::WhiteWins);
+
+    let board = utils::make_board_for(
+        &rules::Giveaway,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' b ' * ' * '",
+            "' b ' * ' * ' *",
+            "w ' * ' * ' * '",
+        ],
+    );
+    assert_eq!(board.result(), GameResult::WhiteWins);
+}
+
+#[test]
+fn capturing_the_last_piece_wins() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let board = board.make_move(Square::at(4, 1), Square::at(2, 3)).unwrap();
+
+    assert_eq!(board.result(), GameResult::WhiteWins);
+}
+
+#[test]
+fn capturing_is_mandatory_for_the_whole_side() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' w '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    assert!(board.moves_for(Square::at(5, 6)).is_empty());
+    assert_eq!(
+        board.make_move(Square::at(5, 6), Square::at(4, 5)).err(),
+        Some(InvalidMove::CaptureRequired)
+    );
+    assert!(board.make_move(Square::at(4, 1), Square::at(2, 3)).is_ok());
+}
+
+#[test]
+fn capturing_is_optional_with_the_optional_policy() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' w '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ])
+    .with_capture_policy(CapturePolicy::Optional);
+
+    assert_eq!(board.get_movable_pieces(Color::White).count(), 2);
+    assert_eq!(board.moves_for(Square::at(4, 1)).len(), 2);
+    let board = board.make_move(Square::at(5, 6), Square::at(4, 5)).unwrap();
+    assert_eq!(board.turn(), Color::Black);
+    assert_eq!(board.huffable_pieces().count(), 0);
+}
+
+#[test]
+fn piece_skipping_a_capture_can_be_huffed() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' w '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ])
+    .with_capture_policy(CapturePolicy::Huffing);
+
+    let board = board.make_move(Square::at(5, 6), Square::at(4, 5)).unwrap();
+    assert_eq!(
+        board.huffable_pieces().collect::<Vec<_>>(),
+        vec![game_piece!(Color::White, false, 4, 1)]
+    );
+    assert_eq!(
+        board.huff(Square::at(4, 5)).err(),
+        Some(InvalidMove::NotHuffable)
+    );
+    assert_eq!(
+        board.huff(Square::at(3, 2)).err(),
+        Some(InvalidMove::WrongTurn)
+    );
+
+    let board = board.huff(Square::at(4, 1)).unwrap();
+    assert_eq!(board.get(Square::at(4, 1)), None);
+    assert_eq!(board.turn(), Color::Black);
+    assert!(board.huff(Square::at(4, 1)).is_err());
+}
+
+#[test]
+fn huffing_is_only_possible_right_after_the_skipped_capture() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' w '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ])
+    .with_capture_policy(CapturePolicy::Huffing);
+
+    let board = board.make_move(Square::at(4, 1), Square::at(3, 0)).unwrap();
+    assert_eq!(
+        board.huffable_pieces().collect::<Vec<_>>(),
+        vec![game_piece!(Color::White, false, 3, 0)]
+    );
+    let board = board.make_move(Square::at(3, 2), Square::at(4, 3)).unwrap();
+    assert_eq!(board.huffable_pieces().count(), 0);
+}
+
+#[test]
+fn constructs_an_international_starting_board_with_20_pieces() {
+    let board = Board::for_rules(&rules::International);
+
+    assert_eq!(board.size(), 10);
+    assert_eq!(board.count_pieces(Color::White), 20);
+    assert_eq!(board.count_pieces(Color::Black), 20);
+    assert_eq!(board.get(Square::at(3, 0)).unwrap().color, Color::Black);
+    assert_eq!(board.get(Square::at(6, 9)).unwrap().color, Color::White);
+    assert!(board.get(Square::at(4, 1)).is_none());
+    assert!(board.get(Square::at(0, 10)).is_none());
+}
+
+#[test]
+fn international_man_passing_the_last_row_is_not_crowned() {
+    let board = utils::make_board_for(
+        &rules::International,
+        &[
+            "' * ' * ' * ' * ' *",
+            "* ' * ' b ' b ' * '",
+            "' * ' w ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+        ],
+    );
+
+    let board = board.make_move(Square::at(2, 3), Square::at(0, 5)).unwrap();
+
+    assert!(!board.get(Square::at(0, 5)).unwrap().is_king);
+    assert_eq!(board.count_pieces(Color::Black), 2);
+    let captured: Vec<_> = board.captured_pieces().collect();
+    assert_eq!(captured, vec![game_piece!(Color::Black, false, 1, 4)]);
+
+    let board = board.make_move(Square::at(0, 5), Square::at(2, 7)).unwrap();
+
+    assert!(!board.get(Square::at(2, 7)).unwrap().is_king);
+    assert_eq!(board.count_pieces(Color::Black), 0);
+    assert_eq!(board.captured_pieces().count(), 0);
+    assert_eq!(board.turn(), Color::Black);
+}
+
+#[test]
+fn international_man_ending_on_the_last_row_is_crowned() {
+    let board = utils::make_board_for(
+        &rules::International,
+        &[
+            "' * ' * ' * ' * ' *",
+            "* ' w ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' b '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+        ],
+    );
+
+    let board = board.make_move(Square::at(1, 2), Square::at(0, 1)).unwrap();
+
+    assert!(board.get(Square::at(0, 1)).unwrap().is_king);
+}
+
+#[test]
+fn russian_man_crowned_during_a_capture_continues_as_king() {
+    let board = utils::make_board_for(
+        &rules::Russian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' b ' * ' * '",
+            "' w ' * ' b ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    );
+
+    let board = board.make_move(Square::at(2, 1), Square::at(0, 3)).unwrap();
+
+    assert!(board.get(Square::at(0, 3)).unwrap().is_king);
+    assert_eq!(
+        board.capturing_piece(),
+        Some(game_piece!(Color::White, true, 0, 3))
+    );
+
+    let board = board.make_move(Square::at(0, 3), Square::at(4, 7)).unwrap();
+
+    assert!(board.get(Square::at(4, 7)).unwrap().is_king);
+    assert_eq!(board.count_pieces(Color::Black), 0);
+}
+
+#[test]
+fn turkish_captures_are_removed_at_once() {
+    let board = utils::make_board_for(
+        &rules::Turkish,
+        &[
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * b *",
+            "* * * * * * * *",
+            "* b * W * b * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+        ],
+    );
+
+    let board = board.make_move(Square::at(4, 3), Square::at(4, 6)).unwrap();
+
+    assert_eq!(board.get(Square::at(4, 5)), None);
+    assert_eq!(board.turn(), Color::White);
+    let moves: Vec<_> = board
+        .moves_for(Square::at(4, 6))
+        .iter()
+        .map(|m| (m.row, m.col))
+        .collect();
+    assert_eq!(moves, vec![(1, 6), (0, 6)]);
+}
+
+#[test]
+fn frisian_king_makes_at_most_three_quiet_moves_in_a_row() {
+    let board = utils::make_board_for(
+        &rules::Frisian,
+        &[
+            "' b ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "W ' w ' * ' * ' * '",
+        ],
+    );
+
+    let board = board.make_move(Square::at(9, 0), Square::at(8, 1)).unwrap();
+    let board = board.make_move(Square::at(0, 1), Square::at(1, 0)).unwrap();
+    let board = board.make_move(Square::at(8, 1), Square::at(9, 0)).unwrap();
+    let board = board.make_move(Square::at(1, 0), Square::at(2, 1)).unwrap();
+    let board = board.make_move(Square::at(9, 0), Square::at(8, 1)).unwrap();
+    let board = board.make_move(Square::at(2, 1), Square::at(3, 0)).unwrap();
+
+    assert_eq!(
+        board.king_streak(Color::White),
+        Some((Square { row: 8, col: 1 }, 3))
+    );
+    assert!(board.moves_for(Square::at(8, 1)).is_empty());
+    assert!(!board.moves_for(Square::at(9, 2)).is_empty());
+
+    let board = board.make_move(Square::at(9, 2), Square::at(8, 3)).unwrap();
+    let board = board.make_move(Square::at(3, 0), Square::at(4, 1)).unwrap();
+    assert_eq!(board.king_streak(Color::White), None);
+    assert!(!board.moves_for(Square::at(8, 1)).is_empty());
+}
+
+#[test]
+fn frisian_king_streak_ends_when_the_king_is_captured() {
+    let board = utils::make_board_for(
+        &rules::Frisian,
+        &[
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' b ' * ' * ' *",
+            "* ' b ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "W ' * ' w ' * ' * '",
+        ],
+    );
+
+    let board = board.make_move(Square::at(9, 0), Square::at(8, 1)).unwrap();
+    assert_eq!(
+        board.king_streak(Color::White),
+        Some((Square { row: 8, col: 1 }, 1))
+    );
+    let board = board.make_move(Square::at(7, 2), Square::at(9, 0)).unwrap();
+
+    assert!(board.get(Square::at(8, 1)).is_none());
+    assert_eq!(board.king_streak(Color::White), None);
+    assert!(board.to_string().parse::<Board>().ok() == Some(board));
+}
+
+#[test]
+fn official_setup_lets_black_move_first() {
+    let board = Board::for_setup(&rules::American, Setup::official());
+
+    assert_eq!(board.turn(), Color::Black);
+    assert_eq!(board.get(Square::at(0, 1)).unwrap().color, Color::Black);
+    assert_eq!(board.get(Square::at(7, 0)).unwrap().color, Color::White);
+    let moves: Vec<_> = board
+        .moves_for(Square::at(2, 1))
+        .iter()
+        .map(|m| (m.row, m.col))
+        .collect();
+    assert_eq!(moves, vec![(3, 0), (3, 2)]);
+}
+
+#[test]
+fn white_can_start_at_the_top() {
+    let setup = Setup::new(Color::White, Color::White);
+    let board = Board::for_setup(&rules::American, setup);
+
+    assert_eq!(board.turn(), Color::White);
+    assert_eq!(board.get(Square::at(0, 1)).unwrap().color, Color::White);
+    assert_eq!(board.get(Square::at(7, 0)).unwrap().color, Color::Black);
+    let moves: Vec<_> = board
+        .moves_for(Square::at(2, 1))
+        .iter()
+        .map(|m| (m.row, m.col))
+        .collect();
+    assert_eq!(moves, vec![(3, 0), (3, 2)]);
+    assert!(board.is_promotion_row(Color::White, 7));
+    assert!(board.is_promotion_row(Color::Black, 0));
+}
+
+#[test]
+fn pool_man_passes_through_the_last_row() {
+    let mut board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' b ' b ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    board.rules = &rules::Pool;
+
+    let board = board.make_move(Square::at(2, 1), Square::at(0, 3)).unwrap();
+    assert_eq!(board.turn(), Color::White);
+    assert!(!board.get(Square::at(0, 3)).unwrap().is_king);
+
+    let board = board.make_move(Square::at(0, 3), Square::at(2, 5)).unwrap();
+    assert_eq!(board.turn(), Color::Black);
+    assert!(!board.get(Square::at(2, 5)).unwrap().is_king);
+}
+
+#[test]
+fn lists_complete_legal_moves_for_a_side() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' b ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' w '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    assert_eq!(
+        board.legal_moves(Color::White),
+        vec![LegalMove {
+            origin: Square { row: 4, col: 1 },
+            sequence: MoveSequence {
+                movement_type: MovementType::Forced,
+                path: vec![Square { row: 2, col: 3 }],
+                captures: vec![Square { row: 3, col: 2 }],
+            },
+        }]
+    );
+    let black_moves = board.legal_moves(Color::Black);
+    assert_eq!(black_moves.len(), 1);
+    assert_eq!(black_moves[0].origin, Square { row: 3, col: 2 });
+}
+
+#[test]
+fn builds_squares_only_on_playable_squares() {
+    assert_eq!(Square::new(&rules::American, 0, 1).map(Square::row), Ok(0));
+    assert_eq!(
+        Square::new(&rules::American, 0, 0),
+        Err(InvalidMove::LightSquare)
+    );
+    assert_eq!(
+        Square::new(&rules::American, 8, 1),
+        Err(InvalidMove::OffBoard)
+    );
+    assert!(Square::new(&rules::International, 9, 0).is_ok());
+}
+
+#[test]
+fn numbers_and_names_squares() {
+    let first = Square::from_number(&rules::American, 1).unwrap();
+    assert_eq!((first.row(), first.col()), (0, 1));
+    assert_eq!(first.name(&rules::American), Some("b8".to_string()));
+
+    let corner = Square::from_name(&rules::American, "a1").unwrap();
+    assert_eq!((corner.row(), corner.col()), (7, 0));
+    assert_eq!(corner.number(&rules::American), Some(29));
+
+    assert_eq!(
+        Square::from_number(&rules::American, 33),
+        Err(InvalidMove::OffBoard)
+    );
+    assert_eq!(
+        Square::from_name(&rules::American, "a9"),
+        Err(InvalidMove::OffBoard)
+    );
+    assert_eq!(
+        Square::from_name(&rules::American, "a2"),
+        Err(InvalidMove::LightSquare)
+    );
+    for number in 1..=50 {
+        let square = Square::from_number(&rules::International, number).unwrap();
+        let name = square.name(&rules::International).unwrap();
+        let named = Square::from_name(&rules::International, &name).unwrap();
+        assert_eq!(named.number(&rules::International), Some(number));
+    }
+}
+
+#[test]
+fn shifts_whole_sets_of_squares() {
+    use crate::rules::Direction::*;
+    let geometry = Geometry::for_rules(&rules::American);
+    // Square 1 of an American board is (0, 1), square 5 is (1, 0).
+    assert_eq!(geometry.step(0, (Increase, Decrease)), Some(4));
+    assert_eq!(geometry.step(0, (Increase, Increase)), Some(5));
+    assert_eq!(geometry.step(4, (Increase, Increase)), Some(8));
+    assert_eq!(geometry.step(0, (Decrease, Increase)), None);
+    assert_eq!(geometry.step(4, (Increase, Decrease)), None);
+    assert_eq!(geometry.shift(0b11, (Increase, Increase)), 0b1100000);
+
+    let geometry = Geometry::for_rules(&rules::Frisian);
+    assert_eq!(geometry.step(0, (Keep, Increase)), Some(1));
+    assert_eq!(geometry.step(0, (Increase, Keep)), Some(10));
+    assert_eq!(geometry.step(4, (Keep, Increase)), None);
+
+    let geometry = Geometry::for_rules(&rules::Turkish);
+    assert_eq!(geometry.step(0, (Increase, Keep)), Some(8));
+    assert_eq!(geometry.step(7, (Keep, Increase)), None);
+    assert_eq!(geometry.squares, u64::MAX);
+}
+
+/// A 12x12 board, whose playable squares do not fit in a bitboard.
+struct Oversized;
+
+impl RuleSet for Oversized {
+    fn name(&self) -> &'static str {
+        "oversized"
+    }
+
+    fn size(&self) -> usize {
+        12
+    }
+}
+
+#[test]
+#[should_panic(expected = "a 12x12 board does not fit in a bitboard")]
+fn refuses_boards_larger_than_a_bitboard() {
+    Board::for_rules(&Oversized);
+}
+
+#[test]
+fn keeps_pieces_in_bitboards() {
+    let board = Board::for_rules(&rules::International);
+    assert_eq!(board.pieces[Color::Black as usize], (1 << 20) - 1);
+    assert_eq!(board.pieces[Color::White as usize], ((1 << 20) - 1) << 30);
+    assert_eq!(board.kings, 0);
+    assert_eq!(
+        board.piece_at(0),
+        Some(Piece {
+            color: Color::Black,
+            is_king: false,
+        })
+    );
+    assert_eq!(board.piece_at(25), None);
+}
+
+/// Hashes the position from scratch.
+fn full_hash(board: &Board) -> u64 {
+    board
+        .all_pieces()
+        .fold(zobrist::turn_key(board.turn()), |hash, piece| {
+            let index = board.index_of(piece.square());
+            let piece = Piece {
+                color: piece.color,
+                is_king: piece.is_king,
+            };
+            hash ^ zobrist::piece_key(piece, index)
+        })
+}
+
+#[test]
+fn keeps_the_hash_up_to_date() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' * ' b ' * '",
+        "' * ' * ' * ' *",
+        "* ' w ' * ' * '",
+        "' W ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    assert_eq!(board.zobrist_hash(), full_hash(&board));
+
+    let board = board.make_move(Square::at(5, 2), Square::at(4, 3)).unwrap();
+    assert_eq!(board.zobrist_hash(), full_hash(&board));
+    let board = board.make_move(Square::at(3, 4), Square::at(5, 2)).unwrap();
+    assert_eq!(board.zobrist_hash(), full_hash(&board));
+    let board = board.with_turn(Color::White);
+    assert_eq!(board.zobrist_hash(), full_hash(&board));
+}
+
+#[test]
+fn hashes_transpositions_alike() {
+    let board = Board::new();
+    let first = board
+        .make_move(Square::at(5, 0), Square::at(4, 1))
+        .and_then(|b| b.make_move(Square::at(2, 1), Square::at(3, 0)))
+        .and_then(|b| b.make_move(Square::at(5, 6), Square::at(4, 7)))
+        .unwrap();
+    let second = board
+        .make_move(Square::at(5, 6), Square::at(4, 7))
+        .and_then(|b| b.make_move(Square::at(2, 1), Square::at(3, 0)))
+        .and_then(|b| b.make_move(Square::at(5, 0), Square::at(4, 1)))
+        .unwrap();
+
+    assert!(first == second);
+    assert_eq!(first.zobrist_hash(), second.zobrist_hash());
+    assert_ne!(first.zobrist_hash(), board.zobrist_hash());
+    let positions: std::collections::HashSet<_> = vec![first, second, board].into_iter().collect();
+    assert_eq!(positions.len(), 2);
+}
+
+#[test]
+fn hashes_the_same_in_every_build() {
+    assert_eq!(Board::new().zobrist_hash(), 0xa531_950d_9924_44e9);
+}
+
+#[test]
+fn undoes_a_capture_sequence_with_crowning() {
+    let board = utils::make_board_for(
+        &rules::Russian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' w ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    );
+    let mut played = board;
+    let first = played.apply(Square::at(5, 2), Square::at(3, 4)).unwrap();
+    assert_eq!(played.turn(), Color::White);
+    let second = played.apply(Square::at(3, 4), Square::at(1, 2)).unwrap();
+    assert_eq!(played.count_pieces(Color::Black), 0);
+    assert_eq!(played.turn(), Color::Black);
+
+    played.undo(second);
+    assert_eq!(
+        played.capturing_piece().map(|p| p.square()),
+        Some(Square::at(3, 4))
+    );
+    played.undo(first);
+    assert!(played == board);
+    assert_eq!(played.zobrist_hash(), board.zobrist_hash());
+}
+
+#[test]
+fn undoes_a_crowning_move() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "w ' * ' * ' * '",
+        "' * ' * ' * ' b",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    let mut played = board;
+    let undo = played.apply(Square::at(1, 0), Square::at(0, 1)).unwrap();
+    assert!(played.get(Square::at(0, 1)).unwrap().is_king);
+
+    played.undo(undo);
+    assert!(played == board);
+    assert!(!played.get(Square::at(1, 0)).unwrap().is_king);
+}
+
+#[test]
+fn prints_and_parses_diagrams() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' B ' * ' *",
+        "* ' w ' * ' * '",
+        "' W ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ])
+    .with_turn(Color::Black);
+    let diagram = board.to_string();
+    assert!(diagram.starts_with("' * ' * ' * ' *\n"));
+    assert!(diagram.ends_with("american, black to move"));
+    assert!(diagram.parse::<Board>().ok() == Some(board));
+
+    for rules in rules::RULESETS.iter() {
+        let board = Board::for_rules(*rules);
+        let first = &board.legal_moves(Color::White)[0];
+        let board = board
+            .make_move(first.origin, first.sequence.path[0])
+            .unwrap();
+        assert!(board.to_string().parse::<Board>().ok() == Some(board));
+    }
+
+    let board = Board::for_setup(&rules::American, Setup::official())
+        .with_capture_policy(CapturePolicy::Huffing);
+    let diagram = board.to_string();
+    assert!(diagram.ends_with(
+        "american, black to move\n\
+         setup: black on top, black moves first\n\
+         captures: huffing"
+    ));
+    assert!(diagram.parse::<Board>().ok() == Some(board));
+
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' w ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ])
+    .with_capture_policy(CapturePolicy::Optional)
+    .make_move(Square::at(5, 2), Square::at(3, 4))
+    .unwrap();
+    let diagram = board.to_string();
+    assert!(diagram.ends_with(
+        "american, white to move\n\
+         captures: optional\n\
+         capturing: e5 up right\n\
+         captured: d4"
+    ));
+    let parsed: Board = diagram.parse().unwrap();
+    assert!(parsed == board);
+    let board = parsed
+        .make_move(Square::at(3, 4), Square::at(1, 2))
+        .unwrap();
+    assert_eq!(board.count_pieces(Color::Black), 0);
+
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' w ' * ' w '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ])
+    .with_capture_policy(CapturePolicy::Huffing)
+    .make_move(Square::at(5, 6), Square::at(4, 7))
+    .unwrap();
+    let diagram = board.to_string();
+    assert!(diagram.ends_with("huffable: c3"));
+    assert!(diagram.parse::<Board>().ok() == Some(board));
+}
+
+#[test]
+fn explains_why_a_diagram_can_not_be_read() {
+    let error = |diagram: &str| diagram.parse::<Board>().err();
+
+    assert_eq!(
+        error("' * ' *\n* ' * '"),
+        Some(ParseBoardError::WrongRowCount {
+            expected: 8,
+            found: 2
+        })
+    );
+    let mut rows = ["' * ' * ' * ' *", "* ' * ' * ' * '"].repeat(4);
+    rows[3] = "w ' * ' * ' * ' *";
+    assert_eq!(
+        error(&rows.join("\n")),
+        Some(ParseBoardError::WrongColumnCount {
+            row: 3,
+            expected: 8,
+            found: 9
+        })
+    );
+    rows[3] = "* w * ' * ' * '";
+    assert_eq!(
+        error(&rows.join("\n")),
+        Some(ParseBoardError::PieceOnLightSquare { row: 3, col: 1 })
+    );
+    rows[3] = "* * * ' * ' * '";
+    assert_eq!(
+        error(&rows.join("\n")),
+        Some(ParseBoardError::WrongSquare { row: 3, col: 1 })
+    );
+    rows[3] = "* ' x ' * ' * '";
+    assert_eq!(
+        error(&rows.join("\n")),
+        Some(ParseBoardError::UnknownSymbol {
+            row: 3,
+            col: 2,
+            symbol: "x".to_string()
+        })
+    );
+    rows[3] = "* ' * ' * ' * '";
+    assert_eq!(
+        error(&(rows.join("\n") + "\nchess, white to move")),
+        Some(ParseBoardError::UnknownRules("chess".to_string()))
+    );
+    assert!(error(&(rows.join("\n") + "\npool, black to move")).is_none());
+    assert_eq!(
+        error(&(rows.join("\n") + "\namerican, white to move\ncapturing: d4 up")),
+        Some(ParseBoardError::BadState("capturing: d4 up".to_string()))
+    );
+    rows[4] = "' * ' w ' * ' *";
+    assert_eq!(
+        error(&(rows.join("\n") + "\namerican, white to move\ncapturing: d4 up")),
+        Some(ParseBoardError::CaptureOver)
+    );
+}
+
+#[test]
+fn rotates_the_board_with_the_colors_swapped() {
+    let board = Board::new();
+    let rotated = board.rotated();
+    assert_eq!(rotated.turn(), Color::Black);
+    assert!(rotated.with_turn(Color::White) == board);
+
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' w ' * ' * '",
+        "' W ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    let rotated = board.rotated();
+    assert_eq!(
+        rotated.get(Square::at(1, 6)),
+        Some(GamePiece {
+            color: Color::Black,
+            is_king: true,
+            row: 1,
+            col: 6,
+        })
+    );
+    assert_eq!(
+        rotated.get(Square::at(5, 4)).map(|p| p.color),
+        Some(Color::White)
+    );
+    assert!(rotated.rotated() == board);
+    assert_eq!(rotated.rotated().zobrist_hash(), board.zobrist_hash());
+}
+
+#[test]
+fn mirrors_only_boards_that_allow_it() {
+    assert!(Board::new().mirrored().is_none());
+    assert!(Board::for_rules(&rules::International).mirrored().is_none());
+
+    let board = utils::make_board_for(
+        &rules::Turkish,
+        &[
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* b * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * W *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+        ],
+    );
+    let mirrored = board.mirrored().unwrap();
+    assert_eq!(
+        mirrored.get(Square::at(2, 6)).map(|p| p.color),
+        Some(Color::Black)
+    );
+    assert!(mirrored.get(Square::at(5, 1)).unwrap().is_king);
+    assert!(mirrored.mirrored() == Some(board));
+}
+
+#[test]
+fn finds_the_same_representative_for_symmetric_positions() {
+    let board = utils::make_board_for(
+        &rules::Turkish,
+        &[
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* b * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * w W *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+        ],
+    );
+    let (canonical, symmetry) = board.canonical();
+    assert!(board.transformed(symmetry) == Some(canonical));
+    for other in [board.mirrored().unwrap(), board.rotated()].iter() {
+        assert!(other.canonical().0 == canonical);
+    }
+    let (canonical, symmetry) = Board::new().canonical();
+    assert_eq!(symmetry, Symmetry::Identity);
+    assert!(canonical == Board::new());
+}
+
+#[test]
+fn finds_the_same_representative_when_only_the_king_streaks_differ() {
+    let mut board = utils::make_board_for(
+        &rules::Turkish,
+        &[
+            "* * * * * * * *",
+            "* * * b b * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* W * * * * W *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+        ],
+    );
+    board.king_streaks[Color::White as usize] = Some((Square::at(5, 1), 1));
+    let mirrored = board.mirrored().unwrap();
+    assert!(mirrored != board);
+
+    assert!(mirrored.canonical().0 == board.canonical().0);
+}
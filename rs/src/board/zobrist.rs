@@ -0,0 +1,51 @@
+use super::{Color, Piece, MAX_SQUARES};
+
+/// The keys are drawn from a fixed seed, so a position hashes to the same
+/// value in every build and stored hashes stay valid.
+const SEED: u64 = 0x6368_6563_6b65_7273;
+
+/// One key for every kind of piece on every square, see `piece_key`.
+static PIECE_KEYS: [[u64; MAX_SQUARES]; 4] = generate_piece_keys();
+
+/// Mixed in when Black is to move.
+const BLACK_TO_MOVE: u64 = splitmix(SEED).1;
+
+/// The key of the piece standing on the square with the given index.
+pub(crate) fn piece_key(piece: Piece, index: usize) -> u64 {
+    PIECE_KEYS[piece.color as usize * 2 + piece.is_king as usize][index]
+}
+
+/// The key of the side to move.
+pub(crate) fn turn_key(color: Color) -> u64 {
+    match color {
+        Color::White => 0,
+        Color::Black => BLACK_TO_MOVE,
+    }
+}
+
+const fn generate_piece_keys() -> [[u64; MAX_SQUARES]; 4] {
+    let mut keys = [[0; MAX_SQUARES]; 4];
+    let mut state = splitmix(SEED).0;
+    let mut kind = 0;
+    while kind < 4 {
+        let mut index = 0;
+        while index < MAX_SQUARES {
+            let (next, key) = splitmix(state);
+            keys[kind][index] = key;
+            state = next;
+            index += 1;
+        }
+        kind += 1;
+    }
+    keys
+}
+
+/// One step of the SplitMix64 generator, returning the next state and the
+/// number drawn.
+const fn splitmix(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (state, z ^ (z >> 31))
+}
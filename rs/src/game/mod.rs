@@ -0,0 +1,139 @@
+use crate::board::{Board, DrawReason, GameResult, InvalidMove, Setup, Square};
+use crate::rules::RuleSet;
+
+/// Draw rules applied by a [`Game`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawRules {
+    /// How many times the same position with the same side to move has to
+    /// occur before the game is drawn.
+    pub repetitions: Option<usize>,
+    /// How many moves each side may make without a capture or a man moving
+    /// before the game is drawn.
+    pub move_limit: Option<usize>,
+}
+
+impl Default for DrawRules {
+    fn default() -> Self {
+        DrawRules {
+            repetitions: Some(3),
+            move_limit: Some(40),
+        }
+    }
+}
+
+/// A board together with the history of the game played on it.
+#[derive(Clone)]
+pub struct Game {
+    board: Board,
+    rules: DrawRules,
+    /// Every position in which a turn started, including the current one.
+    history: Vec<Board>,
+    /// Turns made since the last capture or man move.
+    quiet_turns: usize,
+    /// Whether the turn in progress has captured or moved a man.
+    progress: bool,
+}
+
+impl Game {
+    pub fn new(board: Board) -> Game {
+        Game::with_rules(board, Default::default())
+    }
+
+    /// Starts a game from the starting position of the ruleset, set up as
+    /// described by `setup`.
+    pub fn for_setup(rules: &'static dyn RuleSet, setup: Setup) -> Game {
+        Game::new(Board::for_setup(rules, setup))
+    }
+
+    pub fn with_rules(board: Board, rules: DrawRules) -> Game {
+        Game {
+            board,
+            rules,
+            history: vec![board],
+            quiet_turns: 0,
+            progress: false,
+        }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn rules(&self) -> &DrawRules {
+        &self.rules
+    }
+
+    /// Replaces the draw rules. The history played so far counts towards
+    /// the new rules.
+    pub fn set_rules(&mut self, rules: DrawRules) {
+        self.rules = rules;
+    }
+
+    pub fn make_move(&mut self, from: Square, to: Square) -> Result<(), InvalidMove> {
+        if self.result().is_over() {
+            return Err(InvalidMove::GameOver);
+        }
+        let is_man = matches!(self.board.get(from), Some(piece) if !piece.is_king);
+        let board = self.board.make_move(from, to)?;
+        let turn = self.board.turn();
+        let captured =
+            board.count_pieces(turn.opponent()) < self.board.count_pieces(turn.opponent());
+        self.progress |= is_man || captured;
+        self.board = board;
+        if board.capturing_piece().is_none() {
+            self.quiet_turns = if self.progress {
+                0
+            } else {
+                self.quiet_turns + 1
+            };
+            self.progress = false;
+            self.history.push(board);
+        }
+        Ok(())
+    }
+
+    /// Removes a piece of the opponent that skipped a mandatory capture.
+    /// The turn goes on afterwards.
+    pub fn huff(&mut self, square: Square) -> Result<(), InvalidMove> {
+        if self.result().is_over() {
+            return Err(InvalidMove::GameOver);
+        }
+        self.board = self.board.huff(square)?;
+        self.progress = true;
+        Ok(())
+    }
+
+    pub fn result(&self) -> GameResult {
+        match self.board.result() {
+            GameResult::Ongoing if self.draw_reason().is_some() => GameResult::Draw,
+            result => result,
+        }
+    }
+
+    pub fn draw_reason(&self) -> Option<DrawReason> {
+        if self.board.result().is_over() {
+            return None;
+        }
+        if let Some(repetitions) = self.rules.repetitions {
+            let occurrences = self
+                .history
+                .iter()
+                .filter(|&&board| {
+                    board.zobrist_hash() == self.board.zobrist_hash() && board == self.board
+                })
+                .count();
+            if occurrences >= repetitions {
+                return Some(DrawReason::Repetition);
+            }
+        }
+        if let Some(move_limit) = self.rules.move_limit {
+            if self.quiet_turns >= 2 * move_limit {
+                return Some(DrawReason::MoveLimit);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests;
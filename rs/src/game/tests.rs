@@ -0,0 +1,166 @@
+use super::*;
+use crate::board::Color;
+use crate::utils;
+
+fn kings_endgame() -> Board {
+    utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' B ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' W",
+        "* ' * ' * ' * '",
+    ])
+}
+
+fn shuffle(game: &mut Game) {
+    game.make_move(Square::at(6, 7), Square::at(5, 6)).unwrap();
+    game.make_move(Square::at(2, 1), Square::at(3, 2)).unwrap();
+    game.make_move(Square::at(5, 6), Square::at(6, 7)).unwrap();
+    game.make_move(Square::at(3, 2), Square::at(2, 1)).unwrap();
+}
+
+#[test]
+fn new_game_is_ongoing() {
+    let game = Game::new(Board::new());
+
+    assert_eq!(game.result(), GameResult::Ongoing);
+    assert_eq!(game.draw_reason(), None);
+}
+
+#[test]
+fn threefold_repetition_is_a_draw() {
+    let mut game = Game::new(kings_endgame());
+
+    shuffle(&mut game);
+    assert_eq!(game.result(), GameResult::Ongoing);
+
+    shuffle(&mut game);
+    assert_eq!(game.result(), GameResult::Draw);
+    assert_eq!(game.draw_reason(), Some(DrawReason::Repetition));
+    assert_eq!(
+        game.make_move(Square::at(6, 7), Square::at(5, 6))
+            .unwrap_err(),
+        InvalidMove::GameOver
+    );
+}
+
+#[test]
+fn repetition_count_is_configurable() {
+    let rules = DrawRules {
+        repetitions: Some(2),
+        move_limit: None,
+    };
+    let mut game = Game::with_rules(kings_endgame(), rules);
+
+    shuffle(&mut game);
+
+    assert_eq!(game.draw_reason(), Some(DrawReason::Repetition));
+}
+
+#[test]
+fn moves_without_progress_are_a_draw() {
+    let rules = DrawRules {
+        repetitions: None,
+        move_limit: Some(2),
+    };
+    let mut game = Game::with_rules(kings_endgame(), rules);
+
+    game.make_move(Square::at(6, 7), Square::at(5, 6)).unwrap();
+    game.make_move(Square::at(2, 1), Square::at(3, 2)).unwrap();
+    game.make_move(Square::at(5, 6), Square::at(4, 5)).unwrap();
+    assert_eq!(game.result(), GameResult::Ongoing);
+
+    game.make_move(Square::at(3, 2), Square::at(4, 3)).unwrap();
+    assert_eq!(game.result(), GameResult::Draw);
+    assert_eq!(game.draw_reason(), Some(DrawReason::MoveLimit));
+}
+
+#[test]
+fn changing_the_rules_applies_to_the_moves_played() {
+    let mut game = Game::new(kings_endgame());
+
+    shuffle(&mut game);
+    assert_eq!(game.result(), GameResult::Ongoing);
+
+    game.set_rules(DrawRules {
+        repetitions: None,
+        move_limit: Some(2),
+    });
+    assert_eq!(game.result(), GameResult::Draw);
+    assert_eq!(game.draw_reason(), Some(DrawReason::MoveLimit));
+}
+
+#[test]
+fn moving_a_man_resets_the_move_limit() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' B ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' W",
+        "w ' * ' * ' * '",
+    ]);
+    let rules = DrawRules {
+        repetitions: None,
+        move_limit: Some(1),
+    };
+    let mut game = Game::with_rules(board, rules);
+
+    game.make_move(Square::at(6, 7), Square::at(5, 6)).unwrap();
+    game.make_move(Square::at(2, 1), Square::at(3, 2)).unwrap();
+    assert_eq!(game.draw_reason(), Some(DrawReason::MoveLimit));
+
+    let mut game = Game::with_rules(board, rules);
+    game.make_move(Square::at(7, 0), Square::at(6, 1)).unwrap();
+    game.make_move(Square::at(2, 1), Square::at(3, 2)).unwrap();
+    assert_eq!(game.draw_reason(), None);
+    assert_eq!(game.board().turn(), Color::White);
+}
+
+#[test]
+fn capturing_the_last_piece_wins() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' W ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    let mut game = Game::new(board);
+
+    game.make_move(Square::at(4, 1), Square::at(2, 3)).unwrap();
+
+    assert_eq!(game.result(), GameResult::WhiteWins);
+    assert_eq!(game.draw_reason(), None);
+}
+
+#[test]
+fn huffing_keeps_the_turn() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' w '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ])
+    .with_capture_policy(crate::rules::CapturePolicy::Huffing);
+    let mut game = Game::new(board);
+
+    game.make_move(Square::at(5, 6), Square::at(4, 5)).unwrap();
+    game.huff(Square::at(4, 1)).unwrap();
+
+    assert_eq!(game.board().turn(), Color::Black);
+    assert_eq!(game.board().count_pieces(Color::White), 1);
+    assert!(game.make_move(Square::at(3, 2), Square::at(4, 3)).is_ok());
+}
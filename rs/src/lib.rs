@@ -1,11 +1,21 @@
 mod board;
+mod game;
 mod predictor;
 mod rules;
 mod utils;
 
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
 use wasm_bindgen::prelude::*;
 
-pub use board::{Color, GamePiece};
+pub use board::{
+    Color, DrawReason, GamePiece, GameResult, InvalidMove, ParseBoardError, Setup, Square,
+    Symmetry, SYMMETRIES,
+};
+pub use game::{DrawRules, Game};
+pub use rules::{get_rules, CapturePolicy, LegalMove, MoveSequence, MovementType, RuleSet};
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
@@ -15,55 +25,304 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 #[wasm_bindgen]
 pub struct Board {
-    board: crate::board::Board,
+    game: game::Game,
 }
 
 #[wasm_bindgen]
 impl Board {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Board {
+        utils::set_panic_hook();
         Board {
-            board: crate::board::Board::new(),
+            game: game::Game::new(crate::board::Board::new()),
+        }
+    }
+
+    /// Creates a starting board for the ruleset with the given name.
+    pub fn with_rules(name: &str) -> Result<Board, JsValue> {
+        utils::set_panic_hook();
+        match rules::get_rules(name) {
+            Some(rules) => Ok(Board {
+                game: game::Game::new(crate::board::Board::for_rules(rules)),
+            }),
+            None => Err(JsValue::from_str("Unknown rules")),
+        }
+    }
+
+    /// Creates a starting board for the ruleset with the given name, set up
+    /// as described by `setup`.
+    pub fn with_setup(name: &str, setup: Setup) -> Result<Board, JsValue> {
+        utils::set_panic_hook();
+        match rules::get_rules(name) {
+            Some(rules) => Ok(Board {
+                game: game::Game::for_setup(rules, setup),
+            }),
+            None => Err(JsValue::from_str("Unknown rules")),
+        }
+    }
+
+    /// Creates a board from a diagram like the one `diagram` returns.
+    pub fn from_diagram(diagram: &str) -> Result<Board, JsValue> {
+        utils::set_panic_hook();
+        diagram
+            .parse()
+            .map_err(|error: ParseBoardError| JsValue::from_str(&error.to_string()))
+    }
+
+    /// Draws the position as text, one line per row, followed by the rules,
+    /// the side to move and the rest of the state of the game.
+    pub fn diagram(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn setup(&self) -> Setup {
+        self.game.board().setup()
+    }
+
+    pub fn rules_name(&self) -> String {
+        self.game.board().rules().name().to_string()
+    }
+
+    /// The number of rows and columns on the board.
+    pub fn size(&self) -> usize {
+        self.game.board().size()
+    }
+
+    pub fn turn(&self) -> Color {
+        self.game.board().turn()
+    }
+
+    /// A hash of the position that stays the same between builds.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.game.board().zobrist_hash()
+    }
+
+    /// Returns the position mirrored left to right. Boards of dark squares
+    /// with an even size can not be mirrored, they return `undefined`.
+    pub fn mirrored(&self) -> Option<Board> {
+        self.game
+            .board()
+            .mirrored()
+            .map(|board| self.with_position(board))
+    }
+
+    /// Returns the position turned half way round with the colors swapped.
+    pub fn rotated(&self) -> Board {
+        self.with_position(self.game.board().rotated())
+    }
+
+    /// Returns the position transformed by the symmetry, or `undefined` if
+    /// the board does not allow it.
+    pub fn transformed(&self, symmetry: Symmetry) -> Option<Board> {
+        self.game
+            .board()
+            .transformed(symmetry)
+            .map(|board| self.with_position(board))
+    }
+
+    /// Returns the position every position the symmetries of the board lead
+    /// to shares. `canonical_symmetry` turns this position into it.
+    pub fn canonical(&self) -> Board {
+        self.with_position(self.game.board().canonical().0)
+    }
+
+    pub fn canonical_symmetry(&self) -> Symmetry {
+        self.game.board().canonical().1
+    }
+
+    /// Returns the row and column the square at the given row and column
+    /// ends up on after the symmetry, or nothing if the board does not
+    /// allow it.
+    pub fn transform_square(&self, symmetry: Symmetry, row: usize, col: usize) -> Vec<u8> {
+        let rules = self.game.board().rules();
+        match self.square(row, col) {
+            Ok(square) if symmetry.applies_to(rules) => {
+                let square = symmetry.square(rules, square);
+                vec![utils::to_byte(square.row()), utils::to_byte(square.col())]
+            }
+            _ => vec![],
         }
     }
+
+    /// Returns the legal moves of the given side as they are played on the
+    /// board transformed by the symmetry, encoded the way `legal_moves`
+    /// does. Returns nothing if the board does not allow the symmetry.
+    pub fn transformed_legal_moves(&self, color: Color, symmetry: Symmetry) -> Vec<u8> {
+        let rules = self.game.board().rules();
+        if !symmetry.applies_to(rules) {
+            return vec![];
+        }
+        self.game
+            .board()
+            .legal_moves(color)
+            .iter()
+            .flat_map(|m| m.transformed(rules, symmetry).into_vec())
+            .collect()
+    }
+
+    /// Sets the side to move. This starts a new game history from the
+    /// current position.
+    pub fn set_turn(&mut self, color: Color) {
+        let board = self.game.board().with_turn(color);
+        self.game = game::Game::with_rules(board, *self.game.rules());
+    }
+
+    pub fn capture_policy(&self) -> CapturePolicy {
+        self.game.board().capture_policy()
+    }
+
+    /// Sets whether captures are mandatory. This starts a new game history
+    /// from the current position.
+    pub fn set_capture_policy(&mut self, capture_policy: CapturePolicy) {
+        let board = self.game.board().with_capture_policy(capture_policy);
+        self.game = game::Game::with_rules(board, *self.game.rules());
+    }
+
+    /// How many times a position has to occur for the game to be drawn.
+    pub fn repetitions(&self) -> Option<usize> {
+        self.game.rules().repetitions
+    }
+
+    /// How many moves each side may make without progress before the game
+    /// is drawn.
+    pub fn move_limit(&self) -> Option<usize> {
+        self.game.rules().move_limit
+    }
+
+    /// Sets the draw rules, leaving out the ones given as `undefined`. The
+    /// moves played so far count towards them.
+    pub fn set_draw_rules(&mut self, repetitions: Option<usize>, move_limit: Option<usize>) {
+        self.game.set_rules(DrawRules {
+            repetitions,
+            move_limit,
+        });
+    }
+
+    pub fn result(&self) -> GameResult {
+        self.game.result()
+    }
+
+    pub fn draw_reason(&self) -> Option<DrawReason> {
+        self.game.draw_reason()
+    }
+
     pub fn count_pieces(&self, color: Color) -> usize {
-        self.board.count_pieces(color)
+        self.game.board().count_pieces(color)
     }
 
     pub fn get(&self, row: usize, col: usize) -> Option<GamePiece> {
-        self.board.get(row, col)
+        let square = self.square(row, col).ok()?;
+        self.game.board().get(square)
+    }
+
+    /// Returns the piece on the square with the given number, counted from
+    /// 1 as in the standard notation.
+    pub fn get_numbered(&self, number: usize) -> Option<GamePiece> {
+        let square = self.numbered_square(number).ok()?;
+        self.game.board().get(square)
+    }
+
+    /// Returns the piece on the square with the given algebraic name, such
+    /// as `c3`.
+    pub fn get_named(&self, name: &str) -> Option<GamePiece> {
+        let square = self.named_square(name).ok()?;
+        self.game.board().get(square)
+    }
+
+    /// The number of the playable square at the given row and column.
+    pub fn square_number(&self, row: usize, col: usize) -> Option<usize> {
+        let square = self.square(row, col).ok()?;
+        square.number(self.game.board().rules())
+    }
+
+    /// The algebraic name of the square at the given row and column.
+    pub fn square_name(&self, row: usize, col: usize) -> Option<String> {
+        let square = self.square(row, col).ok()?;
+        square.name(self.game.board().rules())
     }
 
     pub fn all_pieces(&self) -> Vec<u8> {
-        self.board
+        self.game
+            .board()
             .all_pieces()
-            .map(|p| p.into_vec())
-            .flatten()
+            .flat_map(|p| p.into_vec())
             .collect()
     }
 
     pub fn pieces(&self, color: Color) -> Vec<u8> {
-        self.board
+        self.game
+            .board()
             .pieces(color)
-            .map(|p| p.into_vec())
-            .flatten()
+            .flat_map(|p| p.into_vec())
             .collect()
     }
 
     pub fn moves_for(&self, row: usize, col: usize) -> Vec<u8> {
-        self.board
-            .moves_for(row, col)
+        let square = match self.square(row, col) {
+            Ok(square) => square,
+            Err(_) => return vec![],
+        };
+        self.game
+            .board()
+            .moves_for(square)
+            .into_iter()
+            .flat_map(|m| m.into_vec())
+            .collect()
+    }
+
+    /// Returns every complete turn for the piece at the given square.
+    ///
+    /// Each sequence is encoded as the movement type, the number of squares
+    /// in the path followed by their rows and columns, and the number of
+    /// captured squares followed by their rows and columns.
+    pub fn sequences_for(&self, row: usize, col: usize) -> Vec<u8> {
+        let square = match self.square(row, col) {
+            Ok(square) => square,
+            Err(_) => return vec![],
+        };
+        self.game
+            .board()
+            .sequences_for(square)
             .into_iter()
-            .map(|m| m.into_vec())
-            .flatten()
+            .flat_map(|s| s.into_vec())
             .collect()
     }
 
+    /// Returns every complete turn for the given side. Each move is encoded
+    /// as the row and column of the piece that makes it, followed by its
+    /// sequence encoded the way `sequences_for` does.
+    pub fn legal_moves(&self, color: Color) -> Vec<u8> {
+        self.game
+            .board()
+            .legal_moves(color)
+            .into_iter()
+            .flat_map(|m| m.into_vec())
+            .collect()
+    }
+
+    pub fn capturing_piece(&self) -> Option<GamePiece> {
+        self.game.board().capturing_piece()
+    }
+
+    /// Returns the pieces the side to move may huff before making its move.
+    pub fn huffable_pieces(&self) -> Vec<u8> {
+        self.game
+            .board()
+            .huffable_pieces()
+            .flat_map(|p| p.into_vec())
+            .collect()
+    }
+
+    pub fn huff(&mut self, row: u8, col: u8) -> Result<(), MoveError> {
+        let square = self.square(row as usize, col as usize)?;
+        Ok(self.game.huff(square)?)
+    }
+
     pub fn get_movable_pieces(&self, color: Color) -> Vec<u8> {
-        self.board
+        self.game
+            .board()
             .get_movable_pieces(color)
-            .map(|p| p.into_vec())
-            .flatten()
+            .flat_map(|p| p.into_vec())
             .collect()
     }
 
@@ -73,20 +332,85 @@ impl Board {
         from_col: u8,
         to_row: u8,
         to_col: u8,
-    ) -> Result<(), JsValue> {
-        match self.board.make_move(
-            from_row as usize,
-            from_col as usize,
-            to_row as usize,
-            to_col as usize,
-        ) {
-            Ok(board) => {
-                self.board = board;
-                Ok(())
-            }
-            Err(_) => Err(JsValue::from_str("Invalid move")),
+    ) -> Result<(), MoveError> {
+        let from = self.square(from_row as usize, from_col as usize)?;
+        let to = self.square(to_row as usize, to_col as usize)?;
+        Ok(self.game.make_move(from, to)?)
+    }
+
+    /// Makes a move between the squares with the given numbers.
+    pub fn make_numbered_move(&mut self, from: usize, to: usize) -> Result<(), MoveError> {
+        let from = self.numbered_square(from)?;
+        let to = self.numbered_square(to)?;
+        Ok(self.game.make_move(from, to)?)
+    }
+
+    /// Makes a move between the squares with the given algebraic names.
+    pub fn make_named_move(&mut self, from: &str, to: &str) -> Result<(), MoveError> {
+        let from = self.named_square(from)?;
+        let to = self.named_square(to)?;
+        Ok(self.game.make_move(from, to)?)
+    }
+}
+
+impl Board {
+    /// A board with the given position and the same draw rules, starting a
+    /// new game history.
+    fn with_position(&self, board: board::Board) -> Board {
+        Board {
+            game: game::Game::with_rules(board, *self.game.rules()),
         }
     }
+
+    fn square(&self, row: usize, col: usize) -> Result<Square, InvalidMove> {
+        Square::new(self.game.board().rules(), row, col)
+    }
+
+    fn numbered_square(&self, number: usize) -> Result<Square, InvalidMove> {
+        Square::from_number(self.game.board().rules(), number)
+    }
+
+    fn named_square(&self, name: &str) -> Result<Square, InvalidMove> {
+        Square::from_name(self.game.board().rules(), name)
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Boards are compared by their positions, the history of the game is left
+/// out.
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        self.game.board() == other.game.board()
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.game.board().hash(state);
+    }
+}
+
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    fn from_str(diagram: &str) -> Result<Board, ParseBoardError> {
+        Ok(Board {
+            game: game::Game::new(diagram.parse()?),
+        })
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.game.board().fmt(f)
+    }
 }
 
 #[wasm_bindgen]
@@ -96,10 +420,14 @@ pub struct Predictor {
 
 #[wasm_bindgen]
 impl Predictor {
+    /// Creates a predictor looking `depth` turns ahead, which must be
+    /// between 1 and 127.
     #[wasm_bindgen(constructor)]
-    pub fn new(board: Board, depth: u8, color: Color) -> Predictor {
-        Predictor {
-            predictor: predictor::Predictor::new(board.board.clone(), depth, color),
+    pub fn new(board: Board, depth: u8) -> Result<Predictor, DepthError> {
+        utils::set_panic_hook();
+        match predictor::Predictor::new(*board.game.board(), depth) {
+            Ok(predictor) => Ok(Predictor { predictor }),
+            Err(predictor::InvalidDepth) => Err(DepthError { depth }),
         }
     }
 
@@ -110,6 +438,7 @@ impl Predictor {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn register_move(
         &mut self,
         own_from_row: u8,
@@ -120,30 +449,64 @@ impl Predictor {
         oponent_from_col: u8,
         oponent_to_row: u8,
         oponent_to_col: u8,
-    ) -> Result<(), JsValue> {
+    ) -> Result<(), MoveError> {
+        let rules = self.predictor.rules();
         let own_move = predictor::Move {
-            from: predictor::Position {
-                row: own_from_row as usize,
-                col: own_from_col as usize,
-            },
-            to: predictor::Position {
-                row: own_to_row as usize,
-                col: own_to_col as usize,
-            },
+            from: Square::new(rules, own_from_row as usize, own_from_col as usize)?,
+            to: Square::new(rules, own_to_row as usize, own_to_col as usize)?,
         };
         let oponent_move = predictor::Move {
-            from: predictor::Position {
-                row: oponent_from_row as usize,
-                col: oponent_from_col as usize,
-            },
-            to: predictor::Position {
-                row: oponent_to_row as usize,
-                col: oponent_to_col as usize,
-            },
+            from: Square::new(rules, oponent_from_row as usize, oponent_from_col as usize)?,
+            to: Square::new(rules, oponent_to_row as usize, oponent_to_col as usize)?,
         };
-        match self.predictor.register_move(own_move, oponent_move) {
-            Ok(_) => Ok(()),
-            Err(_) => Err(JsValue::from_str("Invalid moves supplied"))
-        }
+        Ok(self.predictor.register_move(own_move, oponent_move)?)
+    }
+}
+
+/// The error thrown when a move is rejected.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct MoveError {
+    code: InvalidMove,
+}
+
+#[wasm_bindgen]
+impl MoveError {
+    /// Tells why the move was rejected.
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> InvalidMove {
+        self.code
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.code.message().to_string()
+    }
+}
+
+impl From<InvalidMove> for MoveError {
+    fn from(code: InvalidMove) -> MoveError {
+        MoveError { code }
+    }
+}
+
+/// The error thrown when a predictor can not look as far ahead as asked.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct DepthError {
+    depth: u8,
+}
+
+#[wasm_bindgen]
+impl DepthError {
+    /// The depth the predictor was asked for.
+    #[wasm_bindgen(getter)]
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        "The depth must be between 1 and 127".to_string()
     }
 }
@@ -1,157 +1,190 @@
 use std::collections::HashMap;
-use std::convert::{TryFrom, TryInto};
 
-use crate::{
-    board::{Board, Color},
-    rules::MovementType,
-};
+use crate::board::{Board, Color};
+pub use crate::board::{InvalidMove, Square};
+use crate::rules::RuleSet;
+use crate::utils::to_byte;
 
 pub struct Predictor {
-    depth: u8,
+    /// How many turns ahead to look, counting both sides.
+    plies: u8,
     color: Color,
+    board: Board,
     cache: PredictionCache,
 }
 
 impl Predictor {
-    pub fn new(board: Board, depth: u8, color: Color) -> Predictor {
-        Predictor {
-            depth,
+    /// Creates a predictor that plays for the side to move on `board`,
+    /// looking `depth` of its own turns ahead. The depth must be between 1
+    /// and 127.
+    pub fn new(board: Board, depth: u8) -> Result<Predictor, InvalidDepth> {
+        let plies = depth
+            .checked_mul(2)
+            .and_then(|plies| plies.checked_sub(1))
+            .ok_or(InvalidDepth)?;
+        let color = board.turn();
+        Ok(Predictor {
+            plies,
             color,
-            cache: PredictionCache::new(board),
-        }
+            board,
+            cache: PredictionCache::new(&board, color),
+        })
     }
 
     pub fn get_next_move(&mut self) -> Result<Move, NoMoreMoves> {
-        self.cache.calculate_moves(2 * self.depth - 1, self.color);
+        self.cache
+            .calculate_moves(&mut self.board, self.plies, self.color);
         let result = self
             .cache
             .branches
             .iter()
-            .max_by(|(_, a), (_, b)| a.get_score(self.color).cmp(&b.get_score(self.color)));
+            .max_by_key(|(_, cache)| cache.get_score(self.color));
         match result {
             Some((move_, _)) => Ok(move_.clone()),
             None => Err(NoMoreMoves),
         }
     }
 
+    pub fn rules(&self) -> &'static dyn RuleSet {
+        self.board.rules()
+    }
+
     pub fn register_move(&mut self, own_move: Move, oponent_move: Move) -> Result<(), InvalidMove> {
-        if let Some(mut cache) = self.cache.branches.remove(&own_move) {
-            if let Some(cache) = cache.branches.remove(&oponent_move) {
-                self.cache = cache;
-                return Ok(());
-            }
-        }
-        Err(InvalidMove)
+        let board = check_move(&self.board, &own_move, self.color)?;
+        let board = check_move(&board, &oponent_move, self.color.opponent())?;
+        let cache = self
+            .cache
+            .branches
+            .remove(&own_move)
+            .and_then(|mut cache| cache.branches.remove(&oponent_move));
+        self.board = board;
+        self.cache = cache.unwrap_or_else(|| PredictionCache::new(&board, self.color));
+        Ok(())
     }
 }
 
+/// Makes the move on `board`, making sure it is one of `color`.
+fn check_move(board: &Board, move_: &Move, color: Color) -> Result<Board, InvalidMove> {
+    if matches!(board.get(move_.from), Some(piece) if piece.color != color) {
+        return Err(InvalidMove::OpponentsPiece);
+    }
+    board.make_move(move_.from, move_.to)
+}
+
+/// The tree of positions searched so far. The positions themselves are not
+/// kept, the search walks a single board with `Board::apply` and
+/// `Board::undo` instead.
 struct PredictionCache {
-    board: Board,
+    /// The score of the position as a leaf of the tree.
+    score: i16,
+    /// The side to move in the position.
+    turn: Color,
     branches: HashMap<Move, PredictionCache>,
 }
 
 impl PredictionCache {
-    fn new(board: Board) -> PredictionCache {
+    fn new(board: &Board, color: Color) -> PredictionCache {
         PredictionCache {
-            board,
+            score: get_score(board, color),
+            turn: board.turn(),
             branches: Default::default(),
         }
     }
 
-    fn calculate_moves(&mut self, depth: u8, color: Color) {
-        for piece in self.board.get_movable_pieces(color) {
-            for movement in self.board.moves_for(piece.row, piece.col) {
-                let move_ = Move {
-                    from: Position {
-                        row: piece.row,
-                        col: piece.col,
-                    },
-                    to: Position {
-                        row: movement.row,
-                        col: movement.col,
-                    },
-                };
-                if !self.branches.contains_key(&move_) {
-                    let board = self
-                        .board
-                        .make_move(piece.row, piece.col, movement.row, movement.col)
-                        .unwrap();
-                    self.branches
-                        .insert(move_.clone(), PredictionCache::new(board));
-                }
-                let cache = self.branches.get_mut(&move_).unwrap();
-                let (color, depth) = match movement.movement_type {
-                    MovementType::Free => (
-                        match color {
-                            Color::Black => Color::White,
-                            Color::White => Color::Black,
-                        },
-                        depth - 1,
-                    ),
-                    MovementType::Forced => (color, depth),
-                };
-                if depth > 0 {
-                    cache.calculate_moves(depth, color);
-                }
+    /// Extends the tree below the position on `board` by `depth` turns,
+    /// scoring new positions for `color`. The board is left as it was.
+    fn calculate_moves(&mut self, board: &mut Board, depth: u8, color: Color) {
+        let turn = board.turn();
+        let mut moves: Vec<Move> = vec![];
+        for legal_move in board.legal_moves(turn) {
+            let move_ = Move {
+                from: legal_move.origin,
+                to: legal_move.sequence.path[0],
+            };
+            if !moves.contains(&move_) {
+                moves.push(move_);
             }
         }
-    }
-
-    fn get_score(&self, color: Color) -> u8 {
-        if !self.branches.is_empty() {
-            self.branches
-                .values()
-                .map(|p| p.get_score(color))
-                .max()
-                .unwrap_or(0)
-        } else {
-            if !self
-                .board
-                .get_movable_pieces(color)
-                .collect::<Vec<_>>()
-                .is_empty()
-            {
-                self.board
-                    .pieces(color)
-                    .collect::<Vec<_>>()
-                    .len()
-                    .try_into()
-                    .unwrap()
+        for move_ in moves {
+            let undo = match board.apply(move_.from, move_.to) {
+                Ok(undo) => undo,
+                Err(_) => continue,
+            };
+            let cache = self
+                .branches
+                .entry(move_)
+                .or_insert_with(|| PredictionCache::new(board, color));
+            // A capture sequence that is still in progress is part of
+            // the same turn.
+            let depth = if board.turn() == turn {
+                depth
             } else {
-                0
+                depth - 1
+            };
+            if depth > 0 {
+                cache.calculate_moves(board, depth, color);
             }
+            board.undo(undo);
         }
     }
+
+    /// Scores the tree for `color`, assuming each side picks the move that
+    /// is best for itself.
+    fn get_score(&self, color: Color) -> i16 {
+        let scores = self.branches.values().map(|p| p.get_score(color));
+        let score = if self.turn == color {
+            scores.max()
+        } else {
+            scores.min()
+        };
+        score.unwrap_or(self.score)
+    }
+}
+
+/// The score of a won position, beyond any difference in material.
+const WIN: i16 = i16::MAX;
+
+/// Scores the position for `color`. Having more pieces than the opponent
+/// scores higher, and a side that can not move loses, unless the rules are
+/// played to lose, where both count the other way around.
+fn get_score(board: &Board, color: Color) -> i16 {
+    let has_moves = |color| board.get_movable_pieces(color).next().is_some();
+    let score = if !has_moves(color) {
+        -WIN
+    } else if !has_moves(color.opponent()) {
+        WIN
+    } else {
+        board.count_pieces(color) as i16 - board.count_pieces(color.opponent()) as i16
+    };
+    if board.rules().is_losing_game() {
+        -score
+    } else {
+        score
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Move {
-    pub from: Position,
-    pub to: Position,
+    pub from: Square,
+    pub to: Square,
 }
 
-impl Into<Vec<u8>> for Move {
-    fn into(self) -> Vec<u8> {
+impl From<Move> for Vec<u8> {
+    fn from(move_: Move) -> Vec<u8> {
         vec![
-            u8::try_from(self.from.row).unwrap(),
-            u8::try_from(self.from.col).unwrap(),
-            u8::try_from(self.to.row).unwrap(),
-            u8::try_from(self.to.col).unwrap(),
+            to_byte(move_.from.row),
+            to_byte(move_.from.col),
+            to_byte(move_.to.row),
+            to_byte(move_.to.col),
         ]
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Position {
-    pub row: usize,
-    pub col: usize,
-}
-
 #[derive(Debug)]
 pub struct NoMoreMoves;
 
-#[derive(Debug)]
-pub struct InvalidMove;
+#[derive(Debug, PartialEq)]
+pub struct InvalidDepth;
 
 #[cfg(test)]
 mod tests;
@@ -4,8 +4,8 @@ use crate::{utils, Color};
 macro_rules! movement {
     ($fr:expr, $fc:expr, $tr:expr, $tc:expr) => {
         Move {
-            from: Position { row: $fr, col: $fc },
-            to: Position { row: $tr, col: $tc },
+            from: Square { row: $fr, col: $fc },
+            to: Square { row: $tr, col: $tc },
         }
     };
 }
@@ -21,8 +21,9 @@ fn calculates_the_first_move_with_lookahead_of_one() {
         "w ' * ' w ' w '",
         "' w ' w ' w ' w",
         "w ' w ' w ' w '",
-    ]);
-    let mut predictor = Predictor::new(board, 1, Color::Black);
+    ])
+    .with_turn(Color::Black);
+    let mut predictor = Predictor::new(board, 1).unwrap();
 
     let actual = predictor.get_next_move().unwrap();
     let possibles = vec![
@@ -57,8 +58,9 @@ fn calculates_the_non_stuck_move_with_lookahead_of_one() {
         "* ' * ' w ' w '",
         "' * ' * ' w ' w",
         "* ' * ' * ' * '",
-    ]);
-    let mut predictor = Predictor::new(board, 1, Color::Black);
+    ])
+    .with_turn(Color::Black);
+    let mut predictor = Predictor::new(board, 1).unwrap();
 
     let actual = predictor.get_next_move().unwrap();
     let expected = movement!(3, 6, 4, 5);
@@ -76,8 +78,9 @@ fn calculates_the_optimal_move_with_lookahead_of_two() {
         "* ' w ' * ' * '",
         "' * ' * ' * ' *",
         "* ' * ' * ' * '",
-    ]);
-    let mut predictor = Predictor::new(board, 2, Color::Black);
+    ])
+    .with_turn(Color::Black);
+    let mut predictor = Predictor::new(board, 2).unwrap();
 
     let actual = predictor.get_next_move().unwrap();
     let expected = movement!(3, 4, 4, 5);
@@ -95,20 +98,20 @@ fn can_be_given_moves() {
         "* ' w ' * ' * '",
         "' * ' * ' * ' *",
         "* ' * ' * ' * '",
-    ]);
-    let mut predictor = Predictor::new(board, 2, Color::Black);
+    ])
+    .with_turn(Color::Black);
+    let mut predictor = Predictor::new(board, 2).unwrap();
 
     let actual = predictor.get_next_move().unwrap();
     let expected = movement!(3, 4, 4, 5);
     assert_eq!(actual, expected);
 
-    predictor.register_move(actual, movement!(5, 2, 4, 3)).unwrap();
+    predictor
+        .register_move(actual, movement!(5, 2, 4, 3))
+        .unwrap();
 
     let actual = predictor.get_next_move().unwrap();
-    let possibles = vec![
-        movement!(4, 5, 5, 4),
-        movement!(4, 5, 5, 6),
-    ];
+    let possibles = vec![movement!(4, 5, 5, 4), movement!(4, 5, 5, 6)];
     let mut found = false;
     for possible in possibles {
         if actual == possible {
@@ -120,3 +123,156 @@ fn can_be_given_moves() {
         panic!("The answer {:?} not in possible moves", actual);
     }
 }
+
+#[test]
+fn calculates_a_move_on_an_international_board() {
+    let board = Board::for_rules(&crate::rules::International);
+    let mut predictor = Predictor::new(board, 1).unwrap();
+
+    let actual = predictor.get_next_move().unwrap();
+
+    assert_eq!(actual.from.row, 6);
+    assert!(board
+        .moves_for(actual.from)
+        .iter()
+        .any(|m| m.row == actual.to.row && m.col == actual.to.col));
+}
+
+#[test]
+fn considers_skipping_captures_when_they_are_optional() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' w '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let mut predictor = Predictor::new(board, 1).unwrap();
+    predictor
+        .cache
+        .calculate_moves(&mut predictor.board, 1, predictor.color);
+    assert_eq!(predictor.cache.branches.len(), 1);
+
+    let board = board.with_capture_policy(crate::rules::CapturePolicy::Optional);
+    let mut predictor = Predictor::new(board, 1).unwrap();
+    predictor
+        .cache
+        .calculate_moves(&mut predictor.board, 1, predictor.color);
+    assert_eq!(predictor.cache.branches.len(), 4);
+}
+
+#[test]
+fn gives_pieces_away_in_giveaway() {
+    let diagram = [
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' w ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ];
+    let offer = movement!(5, 4, 4, 3);
+
+    let board = utils::make_board_for(&crate::rules::Giveaway, &diagram);
+    let mut predictor = Predictor::new(board, 2).unwrap();
+    assert_eq!(predictor.get_next_move().unwrap(), offer);
+
+    let board = utils::make_board(diagram);
+    let mut predictor = Predictor::new(board, 2).unwrap();
+    assert_ne!(predictor.get_next_move().unwrap(), offer);
+}
+
+#[test]
+fn calculates_a_move_on_a_turkish_board() {
+    let board = Board::for_rules(&crate::rules::Turkish);
+    let mut predictor = Predictor::new(board, 1).unwrap();
+
+    let actual = predictor.get_next_move().unwrap();
+
+    assert_eq!(actual.from.row, 5);
+    assert!(board
+        .moves_for(actual.from)
+        .iter()
+        .any(|m| m.row == actual.to.row && m.col == actual.to.col));
+}
+
+#[test]
+fn explains_why_registered_moves_are_rejected() {
+    let board = Board::new();
+    let mut predictor = Predictor::new(board, 1).unwrap();
+
+    assert_eq!(
+        predictor
+            .register_move(movement!(2, 1, 3, 0), movement!(5, 2, 4, 1))
+            .unwrap_err(),
+        InvalidMove::OpponentsPiece
+    );
+    assert_eq!(
+        predictor
+            .register_move(movement!(5, 2, 4, 1), movement!(2, 1, 4, 3))
+            .unwrap_err(),
+        InvalidMove::IllegalMove
+    );
+    assert!(predictor
+        .register_move(movement!(5, 2, 4, 1), movement!(2, 1, 3, 0))
+        .is_ok());
+}
+
+#[test]
+fn rejects_depths_it_can_not_search() {
+    assert_eq!(Predictor::new(Board::new(), 0).err(), Some(InvalidDepth));
+    assert_eq!(Predictor::new(Board::new(), 128).err(), Some(InvalidDepth));
+    assert!(Predictor::new(Board::new(), 127).is_ok());
+}
+
+#[test]
+fn leaves_the_board_as_it_was_after_searching() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' b ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' b ' *",
+        "* ' * ' w ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    let mut predictor = Predictor::new(board, 2).unwrap();
+    predictor.get_next_move().unwrap();
+
+    assert!(predictor.board == board);
+    assert_eq!(predictor.board.zobrist_hash(), board.zobrist_hash());
+}
+
+#[test]
+fn does_not_count_on_the_opponent_taking_pieces_it_can_refuse() {
+    let board = utils::make_board_for(
+        &crate::rules::Giveaway,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' b ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' w ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    )
+    .with_capture_policy(crate::rules::CapturePolicy::Optional);
+    let mut predictor = Predictor::new(board, 2).unwrap();
+    predictor
+        .cache
+        .calculate_moves(&mut predictor.board, predictor.plies, predictor.color);
+
+    let offer = &predictor.cache.branches[&movement!(5, 4, 4, 3)];
+    assert!(offer.branches.contains_key(&movement!(3, 2, 5, 4)));
+    assert_eq!(offer.get_score(predictor.color), 0);
+    assert_eq!(predictor.cache.get_score(predictor.color), 0);
+}
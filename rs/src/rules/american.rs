@@ -0,0 +1,12 @@
+use super::RuleSet;
+
+/// English draughts, as played in America: men move and capture forward
+/// only, kings move a single square and every capture is mandatory, but any
+/// of them may be chosen.
+pub struct American;
+
+impl RuleSet for American {
+    fn name(&self) -> &'static str {
+        "american"
+    }
+}
@@ -0,0 +1,32 @@
+use super::{get_all_directions, get_longest_captures, Crowning, Direction, MoveSequence, RuleSet};
+use crate::board::{Board, GamePiece};
+
+/// Brazilian draughts: the rules of international draughts played on an
+/// 8x8 board.
+pub struct Brazilian;
+
+impl RuleSet for Brazilian {
+    fn name(&self) -> &'static str {
+        "brazilian"
+    }
+
+    fn capture_directions(&self, _piece: &GamePiece) -> Vec<(Direction, Direction)> {
+        get_all_directions()
+    }
+
+    fn has_flying_kings(&self) -> bool {
+        true
+    }
+
+    fn crowning(&self) -> Crowning {
+        Crowning::PassesThrough
+    }
+
+    fn order_captures(
+        &self,
+        _board: &Board,
+        captures: Vec<(GamePiece, MoveSequence)>,
+    ) -> Vec<(GamePiece, MoveSequence)> {
+        get_longest_captures(captures)
+    }
+}
@@ -0,0 +1,62 @@
+use super::{
+    get_all_directions, get_orthogonal_directions, Crowning, Direction, MoveSequence, RuleSet,
+};
+use crate::board::{Board, GamePiece};
+
+/// Frisian draughts: a 10x10 board where men and flying kings capture
+/// orthogonally as well as diagonally. The capture worth the most has to be
+/// played, a man counting two and a king three, and a king makes it when
+/// both are worth the same. A king may not make more than three
+/// non-capturing moves in a row while its side still has men.
+pub struct Frisian;
+
+impl RuleSet for Frisian {
+    fn name(&self) -> &'static str {
+        "frisian"
+    }
+
+    fn size(&self) -> usize {
+        10
+    }
+
+    fn capture_directions(&self, _piece: &GamePiece) -> Vec<(Direction, Direction)> {
+        let mut directions = get_all_directions();
+        directions.extend(get_orthogonal_directions());
+        directions
+    }
+
+    fn has_flying_kings(&self) -> bool {
+        true
+    }
+
+    fn crowning(&self) -> Crowning {
+        Crowning::PassesThrough
+    }
+
+    fn order_captures(
+        &self,
+        board: &Board,
+        captures: Vec<(GamePiece, MoveSequence)>,
+    ) -> Vec<(GamePiece, MoveSequence)> {
+        let priority = |(piece, sequence): &(GamePiece, MoveSequence)| {
+            let value: usize = sequence
+                .captures
+                .iter()
+                .map(|p| match board.get(*p) {
+                    Some(p) if p.is_king => 3,
+                    _ => 2,
+                })
+                .sum();
+            (value, piece.is_king)
+        };
+        let best = captures.iter().map(priority).max();
+        captures
+            .into_iter()
+            .filter(|capture| Some(priority(capture)) == best)
+            .collect()
+    }
+
+    fn max_king_moves(&self) -> Option<u8> {
+        Some(3)
+    }
+}
@@ -0,0 +1,16 @@
+use super::RuleSet;
+
+/// Giveaway checkers, also known as antidraughts: moves are the same as in
+/// American checkers, but the side that loses all its pieces or is left
+/// without a move wins.
+pub struct Giveaway;
+
+impl RuleSet for Giveaway {
+    fn name(&self) -> &'static str {
+        "giveaway"
+    }
+
+    fn is_losing_game(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,37 @@
+use super::{get_all_directions, get_longest_captures, Crowning, Direction, MoveSequence, RuleSet};
+use crate::board::{Board, GamePiece};
+
+/// International draughts: a 10x10 board, men capture backwards as well as
+/// forwards, kings fly and the capture that takes the most pieces has to be
+/// played. A man is only crowned when its move ends on the last row.
+pub struct International;
+
+impl RuleSet for International {
+    fn name(&self) -> &'static str {
+        "international"
+    }
+
+    fn size(&self) -> usize {
+        10
+    }
+
+    fn capture_directions(&self, _piece: &GamePiece) -> Vec<(Direction, Direction)> {
+        get_all_directions()
+    }
+
+    fn has_flying_kings(&self) -> bool {
+        true
+    }
+
+    fn crowning(&self) -> Crowning {
+        Crowning::PassesThrough
+    }
+
+    fn order_captures(
+        &self,
+        _board: &Board,
+        captures: Vec<(GamePiece, MoveSequence)>,
+    ) -> Vec<(GamePiece, MoveSequence)> {
+        get_longest_captures(captures)
+    }
+}
@@ -0,0 +1,45 @@
+use std::cmp::Reverse;
+
+use super::{MoveSequence, RuleSet};
+use crate::board::{Board, GamePiece};
+
+/// Italian draughts: men can not capture kings, and captures are chosen by
+/// strict priority. The capture that takes the most pieces has to be
+/// played, then the one made with a king, then the one that takes the most
+/// kings and finally the one that meets a king the earliest.
+pub struct Italian;
+
+impl RuleSet for Italian {
+    fn name(&self) -> &'static str {
+        "italian"
+    }
+
+    fn can_capture(&self, piece: &GamePiece, target: &GamePiece) -> bool {
+        piece.is_king || !target.is_king
+    }
+
+    fn order_captures(
+        &self,
+        board: &Board,
+        captures: Vec<(GamePiece, MoveSequence)>,
+    ) -> Vec<(GamePiece, MoveSequence)> {
+        let priority = |(piece, sequence): &(GamePiece, MoveSequence)| {
+            let kings: Vec<_> = sequence
+                .captures
+                .iter()
+                .map(|p| board.get(*p).is_some_and(|p| p.is_king))
+                .collect();
+            (
+                sequence.captures.len(),
+                piece.is_king,
+                kings.iter().filter(|&&is_king| is_king).count(),
+                Reverse(kings.iter().position(|&is_king| is_king)),
+            )
+        };
+        let best = captures.iter().map(priority).max();
+        captures
+            .into_iter()
+            .filter(|capture| Some(priority(capture)) == best)
+            .collect()
+    }
+}
@@ -1,8 +1,10 @@
-use std::convert::TryFrom;
+use std::cmp::Ordering;
+use std::ops::Range;
 
 use wasm_bindgen::prelude::*;
 
-use crate::board::{Board, Color, GamePiece};
+use crate::board::{indices, Board, Color, GamePiece, Layout, Square, Symmetry, DIRECTIONS};
+use crate::utils::to_byte;
 
 #[wasm_bindgen]
 #[derive(Debug, PartialEq)]
@@ -18,16 +20,96 @@ impl Movement {
     }
 }
 
-impl Into<Vec<u8>> for Movement {
-    fn into(self) -> Vec<u8> {
+impl From<Movement> for Vec<u8> {
+    fn from(movement: Movement) -> Vec<u8> {
         vec![
-            self.movement_type as u8,
-            u8::try_from(self.row).unwrap(),
-            u8::try_from(self.col).unwrap(),
+            movement.movement_type as u8,
+            to_byte(movement.row),
+            to_byte(movement.col),
         ]
     }
 }
 
+/// A complete turn for a single piece.
+///
+/// `path` lists every square the piece lands on, in order, and `captures`
+/// lists every square whose piece is taken along the way. A free move has
+/// a single step in its path and no captures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveSequence {
+    pub movement_type: MovementType,
+    pub path: Vec<Square>,
+    pub captures: Vec<Square>,
+}
+
+impl MoveSequence {
+    /// The same sequence on a board transformed by the symmetry.
+    pub fn transformed(&self, rules: &dyn RuleSet, symmetry: Symmetry) -> MoveSequence {
+        let squares = |squares: &[Square]| {
+            squares
+                .iter()
+                .map(|&square| symmetry.square(rules, square))
+                .collect()
+        };
+        MoveSequence {
+            movement_type: self.movement_type,
+            path: squares(&self.path),
+            captures: squares(&self.captures),
+        }
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<u8> {
+        self.into()
+    }
+}
+
+/// Encodes the sequence as
+/// `[movement_type, path length, path rows and cols..., capture count, capture rows and cols...]`.
+impl From<MoveSequence> for Vec<u8> {
+    fn from(sequence: MoveSequence) -> Vec<u8> {
+        let mut result = vec![sequence.movement_type as u8];
+        for positions in [sequence.path, sequence.captures].iter() {
+            result.push(to_byte(positions.len()));
+            for position in positions {
+                result.push(to_byte(position.row));
+                result.push(to_byte(position.col));
+            }
+        }
+        result
+    }
+}
+
+/// A complete turn together with the square of the piece that makes it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegalMove {
+    pub origin: Square,
+    pub sequence: MoveSequence,
+}
+
+impl LegalMove {
+    /// The same move on a board transformed by the symmetry.
+    pub fn transformed(&self, rules: &dyn RuleSet, symmetry: Symmetry) -> LegalMove {
+        LegalMove {
+            origin: symmetry.square(rules, self.origin),
+            sequence: self.sequence.transformed(rules, symmetry),
+        }
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<u8> {
+        self.into()
+    }
+}
+
+/// Encodes the move as `[origin row, origin col]` followed by the encoding
+/// of its sequence.
+impl From<LegalMove> for Vec<u8> {
+    fn from(move_: LegalMove) -> Vec<u8> {
+        let mut result = vec![to_byte(move_.origin.row), to_byte(move_.origin.col)];
+        result.extend(Vec::from(move_.sequence));
+        result
+    }
+}
+
 #[wasm_bindgen]
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -36,75 +118,357 @@ pub enum MovementType {
     Forced,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
+/// Whether a side that can capture has to do so.
+///
+/// This is a setting of the board rather than of the ruleset, so any
+/// variant can be played casually.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CapturePolicy {
+    /// Captures are mandatory whenever the ruleset says so.
+    Mandatory,
+    /// Captures are never mandatory.
+    Optional,
+    /// Captures are optional, but the opponent may remove a piece that
+    /// skipped a capture the ruleset would have made mandatory.
+    Huffing,
+}
+
+/// What happens when a man reaches the last row in the middle of a capture.
+/// A man whose move ends on the last row is always crowned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Crowning {
+    /// The man is crowned and the turn is over, even if it could have
+    /// captured more.
+    EndsTurn,
+    /// The man is crowned at once and carries on capturing as a king.
+    ContinuesAsKing,
+    /// The man carries on capturing as a man and is only crowned if its
+    /// move ends on the last row.
+    PassesThrough,
+}
+
+/// The way a row or column changes with every step. Diagonal directions
+/// change both, orthogonal ones keep one of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
     Increase,
     Decrease,
+    Keep,
 }
 
-macro_rules! matches(
-    ($e:expr, $p:pat) => (
-        match $e {
-            $p => true,
-            _ => false
+impl Direction {
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Increase => Direction::Decrease,
+            Direction::Decrease => Direction::Increase,
+            Direction::Keep => Direction::Keep,
         }
-    )
-);
+    }
+}
 
-pub fn get_moves(board: &Board, row: usize, col: usize) -> Vec<Movement> {
-    match board.get(row, col) {
-        Some(piece) => {
-            let possibilities = get_possibilities(&piece);
-            let moves = get_moves_from_possibilities(&board, &piece, possibilities);
+/// The rules of a checkers variant.
+///
+/// Everything that differs between variants is decided here, the board and
+/// the move generation in this module only ask the ruleset.
+pub trait RuleSet: Sync {
+    /// The name the ruleset is selected by.
+    fn name(&self) -> &'static str;
 
-            if has_forced_moves(&moves) {
-                moves
-                    .into_iter()
-                    .filter(|m| matches!(m.movement_type, MovementType::Forced))
-                    .collect()
-            } else {
-                moves
-            }
+    /// The number of rows and columns on the board.
+    ///
+    /// The playable squares have to fit in a 64-bit bitboard, so boards of
+    /// dark squares can have at most 11 rows and fully played boards at most
+    /// 8. Building a board for a larger size panics.
+    fn size(&self) -> usize {
+        8
+    }
+
+    /// Which squares of the board the game is played on.
+    fn layout(&self) -> Layout {
+        Layout::Dark
+    }
+
+    /// The directions a piece may move in without capturing.
+    fn move_directions(&self, piece: &GamePiece) -> Vec<(Direction, Direction)> {
+        get_possibilities(piece)
+    }
+
+    /// The directions a piece may capture in.
+    fn capture_directions(&self, piece: &GamePiece) -> Vec<(Direction, Direction)> {
+        self.move_directions(piece)
+    }
+
+    /// Whether kings may move and capture over any number of empty squares.
+    fn has_flying_kings(&self) -> bool {
+        false
+    }
+
+    /// Whether `piece` is allowed to capture `target`.
+    fn can_capture(&self, _piece: &GamePiece, _target: &GamePiece) -> bool {
+        true
+    }
+
+    /// Whether a side that can capture has to do so.
+    fn is_capture_mandatory(&self) -> bool {
+        true
+    }
+
+    /// Picks the captures that may be played out of all the available ones.
+    fn order_captures(
+        &self,
+        _board: &Board,
+        captures: Vec<(GamePiece, MoveSequence)>,
+    ) -> Vec<(GamePiece, MoveSequence)> {
+        captures
+    }
+
+    /// What happens when a man reaches the last row in the middle of a
+    /// capture.
+    fn crowning(&self) -> Crowning {
+        Crowning::EndsTurn
+    }
+
+    /// Whether the goal is to lose: a side without pieces or moves wins the
+    /// game instead of losing it.
+    fn is_losing_game(&self) -> bool {
+        false
+    }
+
+    /// How many non-capturing moves in a row the same king may make while
+    /// its side still has men, if that is limited at all.
+    fn max_king_moves(&self) -> Option<u8> {
+        None
+    }
+
+    /// The rows the pieces of the given color start on.
+    fn starting_rows(&self, color: Color) -> Range<usize> {
+        let rows_per_side = (self.size() - 2) / 2;
+        match color {
+            Color::Black => 0..rows_per_side,
+            Color::White => self.size() - rows_per_side..self.size(),
+        }
+    }
+
+    /// Whether captured pieces leave the board as soon as they are jumped,
+    /// rather than once the turn is over. They can then be passed over
+    /// later in the same capture, but a piece may not turn back on itself
+    /// between two jumps.
+    fn removes_captures_immediately(&self) -> bool {
+        false
+    }
+
+    /// Whether a piece of the given color is crowned on reaching `row`.
+    fn is_promotion_row(&self, color: Color, row: usize) -> bool {
+        match color {
+            Color::White => row == 0,
+            Color::Black => row == self.size() - 1,
         }
-        None => Default::default(),
     }
 }
 
-pub(crate) fn has_forced_moves(movements: &Vec<Movement>) -> bool {
-    movements
-        .iter()
-        .any(|m| matches!(m.movement_type, MovementType::Forced))
+mod american;
+mod brazilian;
+mod frisian;
+mod giveaway;
+mod international;
+mod italian;
+mod pool;
+mod russian;
+mod spanish;
+mod turkish;
+
+pub use american::American;
+pub use brazilian::Brazilian;
+pub use frisian::Frisian;
+pub use giveaway::Giveaway;
+pub use international::International;
+pub use italian::Italian;
+pub use pool::Pool;
+pub use russian::Russian;
+pub use spanish::Spanish;
+pub use turkish::Turkish;
+
+/// All the rulesets that can be selected by name.
+pub static RULESETS: [&dyn RuleSet; 10] = [
+    &American,
+    &Brazilian,
+    &Frisian,
+    &Giveaway,
+    &International,
+    &Italian,
+    &Pool,
+    &Russian,
+    &Spanish,
+    &Turkish,
+];
+
+pub fn get_rules(name: &str) -> Option<&'static dyn RuleSet> {
+    RULESETS.iter().copied().find(|rules| rules.name() == name)
 }
 
-fn get_next(
-    row: usize,
-    col: usize,
-    row_direction: Direction,
-    col_direction: Direction,
-) -> Result<(usize, usize), ()> {
-    use Direction::*;
-    if matches!(row_direction, Decrease) && row <= 0 {
-        Err(())
-    } else if matches!(row_direction, Increase) && row >= 7 {
-        Err(())
-    } else if matches!(col_direction, Decrease) && col <= 0 {
-        Err(())
-    } else if matches!(col_direction, Increase) && col >= 7 {
-        Err(())
+/// Keeps only the captures that take the most pieces.
+pub fn get_longest_captures(
+    captures: Vec<(GamePiece, MoveSequence)>,
+) -> Vec<(GamePiece, MoveSequence)> {
+    let most = captures.iter().map(|(_, s)| s.captures.len()).max();
+    captures
+        .into_iter()
+        .filter(|(_, s)| Some(s.captures.len()) == most)
+        .collect()
+}
+
+/// Returns the first step of every legal turn for the piece at the given
+/// square.
+pub fn get_moves(board: &Board, square: Square) -> Vec<Movement> {
+    let mut moves: Vec<Movement> = vec![];
+    for sequence in get_sequences(board, square) {
+        let step = sequence.path[0];
+        if !moves.iter().any(|m| m.row == step.row && m.col == step.col) {
+            moves.push(Movement {
+                movement_type: sequence.movement_type,
+                row: step.row,
+                col: step.col,
+            });
+        }
+    }
+    moves
+}
+
+/// Returns every complete legal turn the piece at the given square can
+/// make.
+///
+/// Captures are followed to the end, so a piece that can keep jumping
+/// produces one sequence per distinct path. The piece is treated as if its
+/// side was to move, so the capture obligations of the ruleset apply
+/// across all of its pieces.
+pub fn get_sequences(board: &Board, square: Square) -> Vec<MoveSequence> {
+    match board.get(square) {
+        Some(piece) => get_legal_sequences(board, piece.color)
+            .into_iter()
+            .filter(|(p, _)| p.square() == square)
+            .map(|(_, sequence)| sequence)
+            .collect(),
+        None => Default::default(),
+    }
+}
+
+/// Returns every complete legal turn for the given side, together with the
+/// piece that makes it.
+pub fn get_legal_sequences(board: &Board, color: Color) -> Vec<(GamePiece, MoveSequence)> {
+    let rules = board.rules();
+    let pieces: Vec<_> = match board.capturing_piece() {
+        Some(piece) if piece.color == color => vec![piece],
+        Some(_) => vec![],
+        None => board.pieces(color).collect(),
+    };
+    let mut captures = vec![];
+    let mut free = match board.capturing_piece() {
+        Some(_) => vec![],
+        None => get_free_moves(board, color),
+    };
+    let candidates = get_capture_candidates(board, color);
+    for piece in pieces {
+        let index = board.index_of(piece.square());
+        if candidates & (1 << index) != 0 {
+            let mut sequences = vec![];
+            collect_captures(
+                board,
+                1 << index,
+                piece,
+                index,
+                0,
+                &mut vec![],
+                &mut vec![],
+                &mut sequences,
+            );
+            captures.extend(sequences.into_iter().map(|s| (piece, s)));
+        }
+    }
+    if let (Some(limit), Some((king, moves))) = (rules.max_king_moves(), board.king_streak(color)) {
+        let has_men = board.pieces(color).any(|p| !p.is_king);
+        if has_men && moves >= limit {
+            free.retain(|(p, _)| p.row != king.row || p.col != king.col);
+        }
+    }
+    let captures = if captures.is_empty() {
+        captures
     } else {
-        let row = match row_direction {
-            Increase => row + 1,
-            Decrease => row - 1,
-        };
-        let col = match col_direction {
-            Increase => col + 1,
-            Decrease => col - 1,
-        };
-        Ok((row, col))
+        rules.order_captures(board, captures)
+    };
+    if captures.is_empty() || !is_capture_mandatory(board) {
+        let mut sequences = captures;
+        sequences.extend(free);
+        sequences
+    } else {
+        captures
+    }
+}
+
+/// Returns the squares of the pieces of `color` that may have a capture,
+/// found for all of them at once by shifting whole bitboards. A piece that
+/// is left out has none, so the more costly search can skip it.
+fn get_capture_candidates(board: &Board, color: Color) -> u64 {
+    let geometry = board.geometry;
+    let targets = board.pieces[color.opponent() as usize] & !board.captured;
+    let empty = geometry.squares & !board.occupied();
+    let mut candidates = 0;
+    for &direction in DIRECTIONS.iter() {
+        let back = (direction.0.opposite(), direction.1.opposite());
+        candidates |= geometry.shift(targets & geometry.shift(empty, back), back);
+    }
+    if board.rules().has_flying_kings() {
+        candidates |= board.kings;
     }
+    board.pieces[color as usize] & candidates
 }
 
-fn get_possibilities(piece: &GamePiece) -> Vec<(Direction, Direction)> {
+/// Whether the side to move on `board` has to capture when it can.
+pub fn is_capture_mandatory(board: &Board) -> bool {
+    board.capture_policy() == CapturePolicy::Mandatory && board.rules().is_capture_mandatory()
+}
+
+/// Returns the direction of a step or a jump between the two squares.
+pub(crate) fn get_direction(
+    from_row: usize,
+    from_col: usize,
+    to_row: usize,
+    to_col: usize,
+) -> (Direction, Direction) {
+    let direction = |from: usize, to: usize| match to.cmp(&from) {
+        Ordering::Greater => Direction::Increase,
+        Ordering::Less => Direction::Decrease,
+        Ordering::Equal => Direction::Keep,
+    };
+    (direction(from_row, to_row), direction(from_col, to_col))
+}
+
+/// Returns all four diagonal directions.
+pub fn get_all_directions() -> Vec<(Direction, Direction)> {
+    use Direction::*;
+    vec![
+        (Decrease, Decrease),
+        (Decrease, Increase),
+        (Increase, Decrease),
+        (Increase, Increase),
+    ]
+}
+
+/// Returns all four orthogonal directions.
+pub fn get_orthogonal_directions() -> Vec<(Direction, Direction)> {
+    use Direction::*;
+    vec![
+        (Decrease, Keep),
+        (Keep, Decrease),
+        (Keep, Increase),
+        (Increase, Keep),
+    ]
+}
+
+/// Returns the forward directions for men and all four directions for
+/// kings.
+pub fn get_possibilities(piece: &GamePiece) -> Vec<(Direction, Direction)> {
     use Direction::*;
     let direction = match piece.color {
         Color::White => Decrease,
@@ -112,47 +476,185 @@ fn get_possibilities(piece: &GamePiece) -> Vec<(Direction, Direction)> {
     };
     let mut possibilities = vec![(direction, Decrease), (direction, Increase)];
     if piece.is_king {
-        let direction = match direction {
-            Increase => Decrease,
-            Decrease => Increase,
-        };
+        let direction = direction.opposite();
         possibilities.push((direction, Decrease));
         possibilities.push((direction, Increase));
     }
     possibilities
 }
 
-fn get_moves_from_possibilities(
-    board: &Board,
-    piece: &GamePiece,
-    possibilities: Vec<(Direction, Direction)>,
-) -> Vec<Movement> {
+/// Returns the free moves of the pieces of `color`, piece by piece.
+///
+/// The squares the men and the kings can move to are found in every
+/// direction at once by shifting whole bitboards. Flying kings keep
+/// shifting the squares they reached until they run into a piece or off
+/// the board.
+fn get_free_moves(board: &Board, color: Color) -> Vec<(GamePiece, MoveSequence)> {
+    let rules = board.rules();
+    let geometry = board.geometry;
+    let empty = geometry.squares & !board.occupied();
+    let own = board.pieces[color as usize];
     let mut moves = vec![];
-    for (row_direction, col_direction) in possibilities {
-        if let Ok((row, col)) = get_next(piece.row, piece.col, row_direction, col_direction) {
-            match board.get(row, col) {
-                None => moves.push(Movement {
-                    movement_type: MovementType::Free,
-                    row,
-                    col,
-                }),
-                Some(other_piece) => {
-                    if other_piece.color != piece.color {
-                        if let Ok((row, col)) = get_next(row, col, row_direction, col_direction) {
-                            if board.get(row, col).is_none() {
-                                moves.push(Movement {
-                                    movement_type: MovementType::Forced,
-                                    row,
-                                    col,
-                                });
-                            }
-                        }
+    for &is_king in [false, true].iter() {
+        let movers = own & if is_king { board.kings } else { !board.kings };
+        if movers == 0 {
+            continue;
+        }
+        let piece = GamePiece {
+            color,
+            is_king,
+            row: 0,
+            col: 0,
+        };
+        let flying = is_king && rules.has_flying_kings();
+        for direction in rules.move_directions(&board.oriented(&piece)) {
+            let back = (direction.0.opposite(), direction.1.opposite());
+            let mut reached = geometry.shift(movers, direction) & empty;
+            let mut distance = 1;
+            while reached != 0 {
+                for index in indices(reached) {
+                    let mut origin = index;
+                    for _ in 0..distance {
+                        origin = geometry
+                            .step(origin, back)
+                            .expect("the move came from there");
                     }
+                    let (row, col) = geometry.position(index);
+                    let sequence = MoveSequence {
+                        movement_type: MovementType::Free,
+                        path: vec![Square { row, col }],
+                        captures: vec![],
+                    };
+                    moves.push((origin, sequence));
                 }
+                if !flying {
+                    break;
+                }
+                reached = geometry.shift(reached, direction) & empty;
+                distance += 1;
             }
         }
     }
+    // Sorting is stable, so the moves of a piece stay in the order of its
+    // directions.
+    moves.sort_by_key(|&(origin, _)| origin);
     moves
+        .into_iter()
+        .map(|(origin, sequence)| {
+            let piece = board
+                .piece_at_index(origin)
+                .expect("a piece moves from there");
+            (piece, sequence)
+        })
+        .collect()
+}
+
+/// Extends the capture path of `current`, standing on the square with
+/// index `at`, depth first, pushing a sequence for every path that can not
+/// be continued any further. A man reaching the last row is handled as the
+/// ruleset's `Crowning` says.
+///
+/// The pieces a hop can jump and the squares it can land on are found for
+/// each direction by shifting bitboards, `taken` holding the squares of the
+/// pieces in `captures`. Captured pieces stay on the board until the turn
+/// is over, so they can neither be jumped twice nor passed over, unless the
+/// ruleset removes them immediately. The pieces captured earlier in the
+/// turn, before the sequence started, are never jumped again. The square
+/// `origin` the piece started from counts as empty.
+#[allow(clippy::too_many_arguments)]
+fn collect_captures(
+    board: &Board,
+    origin: u64,
+    current: GamePiece,
+    at: usize,
+    taken: u64,
+    path: &mut Vec<Square>,
+    captures: &mut Vec<Square>,
+    sequences: &mut Vec<MoveSequence>,
+) {
+    let rules = board.rules();
+    let geometry = board.geometry;
+    let flying = current.is_king && rules.has_flying_kings();
+    let immediate = rules.removes_captures_immediately();
+    let mut empty = (geometry.squares & !board.occupied()) | origin;
+    if immediate {
+        empty |= taken;
+    }
+    let targets = board.pieces[current.color.opponent() as usize] & !board.captured & !taken;
+    let square = |index: usize| {
+        let (row, col) = geometry.position(index);
+        Square { row, col }
+    };
+    let from = match path.len() {
+        0 => None,
+        1 => Some(square(origin.trailing_zeros() as usize)),
+        length => Some(path[length - 2]),
+    };
+    let heading = match from {
+        Some(from) => Some(get_direction(from.row, from.col, current.row, current.col)),
+        None => board.capture_direction,
+    };
+    let mut extended = false;
+    for direction in rules.capture_directions(&board.oriented(&current)) {
+        let (row_direction, col_direction) = direction;
+        let reverses = matches!(heading, Some((row, col))
+            if row == row_direction.opposite() && col == col_direction.opposite());
+        if immediate && reverses {
+            continue;
+        }
+        let mut over = geometry.shift(1 << at, direction);
+        while flying && over & empty != 0 {
+            over = geometry.shift(over, direction);
+        }
+        if over & targets == 0 {
+            continue;
+        }
+        let over = over.trailing_zeros() as usize;
+        let other_piece = board.piece_at_index(over).expect("a piece is jumped");
+        if !rules.can_capture(&current, &other_piece) {
+            continue;
+        }
+        let mut landings = geometry.shift(1 << over, direction) & empty;
+        while landings != 0 {
+            let index = landings.trailing_zeros() as usize;
+            extended = true;
+            let landing = square(index);
+            path.push(landing);
+            captures.push(square(over));
+            let crowned = !current.is_king && board.is_promotion_row(current.color, landing.row);
+            let next = GamePiece {
+                row: landing.row,
+                col: landing.col,
+                is_king: current.is_king
+                    || (crowned && rules.crowning() == Crowning::ContinuesAsKing),
+                ..current
+            };
+            if crowned && rules.crowning() == Crowning::EndsTurn {
+                sequences.push(MoveSequence {
+                    movement_type: MovementType::Forced,
+                    path: path.clone(),
+                    captures: captures.clone(),
+                });
+            } else {
+                let taken = taken | 1 << over;
+                collect_captures(board, origin, next, index, taken, path, captures, sequences);
+            }
+            path.pop();
+            captures.pop();
+            landings = if flying {
+                geometry.shift(landings, direction) & empty
+            } else {
+                0
+            };
+        }
+    }
+    if !extended && !path.is_empty() {
+        sequences.push(MoveSequence {
+            movement_type: MovementType::Forced,
+            path: path.clone(),
+            captures: captures.clone(),
+        });
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,24 @@
+use super::{get_all_directions, Crowning, Direction, RuleSet};
+use crate::board::GamePiece;
+
+/// Pool checkers: men capture backwards as well as forwards and kings fly,
+/// but any capture may be chosen, not just the longest one.
+pub struct Pool;
+
+impl RuleSet for Pool {
+    fn name(&self) -> &'static str {
+        "pool"
+    }
+
+    fn capture_directions(&self, _piece: &GamePiece) -> Vec<(Direction, Direction)> {
+        get_all_directions()
+    }
+
+    fn has_flying_kings(&self) -> bool {
+        true
+    }
+
+    fn crowning(&self) -> Crowning {
+        Crowning::PassesThrough
+    }
+}
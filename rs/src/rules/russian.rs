@@ -0,0 +1,26 @@
+use super::{get_all_directions, Crowning, Direction, RuleSet};
+use crate::board::GamePiece;
+
+/// Russian draughts (shashki): men capture backwards as well as forwards and
+/// kings fly. A man that reaches the last row during a capture is crowned
+/// at once and goes on capturing as a king. Any capture may be chosen, not
+/// just the longest one.
+pub struct Russian;
+
+impl RuleSet for Russian {
+    fn name(&self) -> &'static str {
+        "russian"
+    }
+
+    fn capture_directions(&self, _piece: &GamePiece) -> Vec<(Direction, Direction)> {
+        get_all_directions()
+    }
+
+    fn has_flying_kings(&self) -> bool {
+        true
+    }
+
+    fn crowning(&self) -> Crowning {
+        Crowning::ContinuesAsKing
+    }
+}
@@ -0,0 +1,41 @@
+use super::{get_longest_captures, MoveSequence, RuleSet};
+use crate::board::{Board, GamePiece, Layout};
+
+/// Spanish draughts: played on a flipped board, men move and capture
+/// forward only and kings fly. The capture that takes the most pieces has
+/// to be played and out of those, the one that takes the most kings.
+pub struct Spanish;
+
+impl RuleSet for Spanish {
+    fn name(&self) -> &'static str {
+        "spanish"
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::Flipped
+    }
+
+    fn has_flying_kings(&self) -> bool {
+        true
+    }
+
+    fn order_captures(
+        &self,
+        board: &Board,
+        captures: Vec<(GamePiece, MoveSequence)>,
+    ) -> Vec<(GamePiece, MoveSequence)> {
+        let kings = |sequence: &MoveSequence| {
+            sequence
+                .captures
+                .iter()
+                .filter(|p| board.get(**p).is_some_and(|p| p.is_king))
+                .count()
+        };
+        let captures = get_longest_captures(captures);
+        let most = captures.iter().map(|(_, s)| kings(s)).max();
+        captures
+            .into_iter()
+            .filter(|(_, s)| Some(kings(s)) == most)
+            .collect()
+    }
+}
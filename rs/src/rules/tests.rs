@@ -1,6 +1,12 @@
 use super::*;
 use crate::utils;
 
+macro_rules! positions {
+    ($(($row:expr, $col:expr)),*) => {
+        vec![$(Square { row: $row, col: $col }),*]
+    };
+}
+
 #[test]
 fn non_king_white_free_movement() {
     let board = utils::make_board([
@@ -14,7 +20,7 @@ fn non_king_white_free_movement() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 2);
+    let moves = get_moves(&board, Square::at(3, 2));
 
     assert_eq!(moves.len(), 2);
     match &moves[0] {
@@ -48,7 +54,7 @@ fn non_king_white_free_movement_blocked_by_friendly() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 2);
+    let moves = get_moves(&board, Square::at(3, 2));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -74,7 +80,7 @@ fn non_king_white_free_movement_blocked_by_left_wall() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 0);
+    let moves = get_moves(&board, Square::at(3, 0));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -100,7 +106,7 @@ fn non_king_white_free_movement_blocked_by_right_wall() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 2, 7);
+    let moves = get_moves(&board, Square::at(2, 7));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -126,7 +132,7 @@ fn non_king_white_free_movement_blocked_by_enemy_and_right_wall() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 6);
+    let moves = get_moves(&board, Square::at(3, 6));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -152,7 +158,7 @@ fn non_king_white_free_movement_blocked_by_enemy_and_left_wall() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 2, 1);
+    let moves = get_moves(&board, Square::at(2, 1));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -178,7 +184,7 @@ fn non_king_white_forced_movement() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 2);
+    let moves = get_moves(&board, Square::at(3, 2));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -204,7 +210,7 @@ fn non_king_white_forced_movement_with_possibilities() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 4);
+    let moves = get_moves(&board, Square::at(3, 4));
 
     assert_eq!(moves.len(), 2);
     match &moves[0] {
@@ -238,7 +244,7 @@ fn non_king_black_free_movement() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 2);
+    let moves = get_moves(&board, Square::at(3, 2));
 
     assert_eq!(moves.len(), 2);
     match &moves[0] {
@@ -272,7 +278,7 @@ fn non_king_black_free_movement_blocked_by_friendly() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 4);
+    let moves = get_moves(&board, Square::at(3, 4));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -298,7 +304,7 @@ fn non_king_black_free_movement_blocked_by_left_wall() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 0);
+    let moves = get_moves(&board, Square::at(3, 0));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -324,7 +330,7 @@ fn non_king_black_free_movement_blocked_by_right_wall() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 4, 7);
+    let moves = get_moves(&board, Square::at(4, 7));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -350,7 +356,7 @@ fn non_king_black_forced_movement() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 3, 2);
+    let moves = get_moves(&board, Square::at(3, 2));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -376,7 +382,7 @@ fn king_white_free_movement() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 4, 3);
+    let moves = get_moves(&board, Square::at(4, 3));
 
     assert_eq!(moves.len(), 4);
     match &moves[0] {
@@ -426,7 +432,7 @@ fn king_white_free_movement_blocked_by_friendly() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 4, 3);
+    let moves = get_moves(&board, Square::at(4, 3));
 
     assert_eq!(moves.len(), 3);
     match &moves[0] {
@@ -468,7 +474,7 @@ fn king_white_forced_movement() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 4, 3);
+    let moves = get_moves(&board, Square::at(4, 3));
 
     assert_eq!(moves.len(), 1);
     match &moves[0] {
@@ -494,7 +500,7 @@ fn king_white_forced_movement_multiple() {
         "* ' * ' * ' * '",
     ]);
 
-    let moves = get_moves(&board, 4, 3);
+    let moves = get_moves(&board, Square::at(4, 3));
 
     assert_eq!(moves.len(), 2);
     match &moves[0] {
@@ -514,3 +520,943 @@ fn king_white_forced_movement_multiple() {
         other => panic!("Unexpected move {:?}", other),
     }
 }
+
+#[test]
+fn sequence_for_free_movement() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' w ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let sequences = get_sequences(&board, Square::at(3, 2));
+
+    assert_eq!(
+        sequences,
+        vec![
+            MoveSequence {
+                movement_type: MovementType::Free,
+                path: positions![(2, 1)],
+                captures: vec![],
+            },
+            MoveSequence {
+                movement_type: MovementType::Free,
+                path: positions![(2, 3)],
+                captures: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn sequence_for_multiple_jumps() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' w ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let sequences = get_sequences(&board, Square::at(5, 2));
+
+    assert_eq!(
+        sequences,
+        vec![MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(3, 4), (1, 2)],
+            captures: positions![(4, 3), (2, 3)],
+        }]
+    );
+}
+
+#[test]
+fn sequences_for_branching_jumps() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' b ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' w ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let sequences = get_sequences(&board, Square::at(5, 2));
+
+    assert_eq!(
+        sequences,
+        vec![
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(3, 4), (1, 2)],
+                captures: positions![(4, 3), (2, 3)],
+            },
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(3, 4), (1, 6)],
+                captures: positions![(4, 3), (2, 5)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn sequence_for_king_does_not_jump_the_same_piece_twice() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' W ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let sequences = get_sequences(&board, Square::at(5, 2));
+
+    assert_eq!(
+        sequences,
+        vec![MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(3, 4)],
+            captures: positions![(4, 3)],
+        }]
+    );
+}
+
+#[test]
+fn sequence_for_king_may_return_to_its_starting_square() {
+    let board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' b ' * '",
+        "' W ' * ' * ' *",
+        "* ' b ' b ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+
+    let sequences = get_sequences(&board, Square::at(4, 1));
+
+    assert_eq!(sequences.len(), 2);
+    assert_eq!(
+        sequences[0],
+        MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(2, 3), (4, 5), (6, 3), (4, 1)],
+            captures: positions![(3, 2), (3, 4), (5, 4), (5, 2)],
+        }
+    );
+    assert_eq!(
+        sequences[1],
+        MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(6, 3), (4, 5), (2, 3), (4, 1)],
+            captures: positions![(5, 2), (5, 4), (3, 4), (3, 2)],
+        }
+    );
+}
+
+struct FlyingKings;
+
+impl RuleSet for FlyingKings {
+    fn name(&self) -> &'static str {
+        "flying kings"
+    }
+
+    fn has_flying_kings(&self) -> bool {
+        true
+    }
+
+    fn is_capture_mandatory(&self) -> bool {
+        false
+    }
+
+    fn order_captures(
+        &self,
+        _board: &Board,
+        captures: Vec<(GamePiece, MoveSequence)>,
+    ) -> Vec<(GamePiece, MoveSequence)> {
+        let most = captures.iter().map(|(_, s)| s.captures.len()).max();
+        captures
+            .into_iter()
+            .filter(|(_, s)| Some(s.captures.len()) == most)
+            .collect()
+    }
+}
+
+/// Men that capture backwards, to tell the crowning rules apart.
+struct BackwardsMen;
+
+impl RuleSet for BackwardsMen {
+    fn name(&self) -> &'static str {
+        "backwards men"
+    }
+
+    fn capture_directions(&self, _piece: &GamePiece) -> Vec<(Direction, Direction)> {
+        get_all_directions()
+    }
+}
+
+#[test]
+fn rules_are_found_by_name() {
+    assert_eq!(get_rules("american").map(|r| r.name()), Some("american"));
+    assert!(get_rules("unknown").is_none());
+}
+
+#[test]
+fn flying_king_free_movement() {
+    let mut board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' W ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    board.rules = &FlyingKings;
+
+    let moves = get_moves(&board, Square::at(6, 1));
+
+    let squares: Vec<_> = moves.iter().map(|m| (m.row, m.col)).collect();
+    assert_eq!(
+        squares,
+        vec![
+            (5, 0),
+            (5, 2),
+            (4, 3),
+            (3, 4),
+            (2, 5),
+            (1, 6),
+            (0, 7),
+            (7, 0),
+            (7, 2)
+        ]
+    );
+}
+
+#[test]
+fn flying_king_captures_from_a_distance() {
+    let mut board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' b ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' W ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    board.rules = &FlyingKings;
+
+    let sequences: Vec<_> = get_sequences(&board, Square::at(6, 1))
+        .into_iter()
+        .filter(|s| s.movement_type == MovementType::Forced)
+        .collect();
+
+    assert_eq!(
+        sequences,
+        vec![
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(2, 5)],
+                captures: positions![(3, 4)],
+            },
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(1, 6)],
+                captures: positions![(3, 4)],
+            },
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(0, 7)],
+                captures: positions![(3, 4)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn optional_captures_keep_free_moves() {
+    let mut board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' b ' * ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    board.rules = &FlyingKings;
+
+    let moves = get_moves(&board, Square::at(4, 1));
+
+    assert_eq!(
+        moves,
+        vec![
+            Movement {
+                movement_type: MovementType::Forced,
+                row: 2,
+                col: 3
+            },
+            Movement {
+                movement_type: MovementType::Free,
+                row: 3,
+                col: 0
+            },
+        ]
+    );
+}
+
+#[test]
+fn captures_are_ordered_by_the_rules() {
+    let mut board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' b ' * ' *",
+        "* ' w ' * ' b '",
+        "' * ' * ' * ' w",
+        "* ' * ' * ' * '",
+    ]);
+
+    let pieces: Vec<_> = get_legal_sequences(&board, Color::White)
+        .into_iter()
+        .map(|(p, _)| (p.row, p.col))
+        .collect();
+    assert_eq!(pieces, vec![(5, 2), (6, 7)]);
+
+    board.rules = &FlyingKings;
+    let sequences: Vec<_> = get_legal_sequences(&board, Color::White)
+        .into_iter()
+        .filter(|(_, s)| s.movement_type == MovementType::Forced)
+        .collect();
+    assert_eq!(sequences.len(), 1);
+    assert_eq!(
+        sequences[0].1,
+        MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(3, 4), (1, 2)],
+            captures: positions![(4, 3), (2, 3)],
+        }
+    );
+}
+
+#[test]
+fn international_men_capture_backwards() {
+    let board = utils::make_board_for(
+        &International,
+        &[
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' w ' * ' *",
+            "* ' * ' * ' b ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+        ],
+    );
+
+    let moves = get_moves(&board, Square::at(4, 5));
+
+    assert_eq!(
+        moves,
+        vec![Movement {
+            movement_type: MovementType::Forced,
+            row: 6,
+            col: 7
+        }]
+    );
+}
+
+#[test]
+fn international_majority_capture_is_mandatory() {
+    let board = utils::make_board_for(
+        &International,
+        &[
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' b ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' b ' * ' b '",
+            "' * ' w ' * ' * ' w",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+        ],
+    );
+
+    let sequences = get_legal_sequences(&board, Color::White);
+
+    assert_eq!(sequences.len(), 1);
+    assert_eq!((sequences[0].0.row, sequences[0].0.col), (6, 3));
+    assert_eq!(
+        sequences[0].1,
+        MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(4, 5), (2, 3)],
+            captures: positions![(5, 4), (3, 4)],
+        }
+    );
+}
+
+#[test]
+fn international_flying_king_lands_where_the_capture_continues() {
+    let board = utils::make_board_for(
+        &International,
+        &[
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' b '",
+            "' * ' b ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "W ' * ' * ' * ' * '",
+        ],
+    );
+
+    let sequences = get_sequences(&board, Square::at(9, 0));
+
+    assert_eq!(
+        sequences,
+        vec![MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(3, 6), (6, 9)],
+            captures: positions![(6, 3), (5, 8)],
+        }]
+    );
+}
+
+#[test]
+fn russian_man_is_crowned_during_a_capture() {
+    let board = utils::make_board_for(
+        &Russian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' b ' * ' * '",
+            "' w ' * ' b ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    );
+
+    let sequences = get_sequences(&board, Square::at(2, 1));
+
+    assert_eq!(
+        sequences,
+        vec![
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(0, 3), (3, 6)],
+                captures: positions![(1, 2), (2, 5)],
+            },
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(0, 3), (4, 7)],
+                captures: positions![(1, 2), (2, 5)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn russian_captures_may_be_chosen_freely() {
+    let board = utils::make_board_for(
+        &Russian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' w ' * ' b '",
+            "' * ' * ' * ' w",
+            "* ' * ' * ' * '",
+        ],
+    );
+
+    let pieces: Vec<_> = get_legal_sequences(&board, Color::White)
+        .into_iter()
+        .map(|(p, s)| (p.row, p.col, s.captures.len()))
+        .collect();
+
+    assert_eq!(pieces, vec![(5, 2, 2), (6, 7, 1)]);
+}
+
+#[test]
+fn italian_man_can_not_capture_a_king() {
+    let board = utils::make_board_for(
+        &Italian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' B ' b ' * '",
+            "' * ' w ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    );
+
+    let moves = get_moves(&board, Square::at(4, 3));
+
+    assert_eq!(
+        moves,
+        vec![Movement {
+            movement_type: MovementType::Forced,
+            row: 2,
+            col: 5
+        }]
+    );
+}
+
+#[test]
+fn italian_prefers_capturing_with_a_king() {
+    let board = utils::make_board_for(
+        &Italian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' b ' * ' b '",
+            "' w ' * ' * ' W",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    );
+
+    let pieces: Vec<_> = get_legal_sequences(&board, Color::White)
+        .into_iter()
+        .map(|(p, _)| (p.row, p.col))
+        .collect();
+
+    assert_eq!(pieces, vec![(4, 7)]);
+}
+
+#[test]
+fn italian_prefers_capturing_the_most_kings() {
+    let board = utils::make_board_for(
+        &Italian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' B ' b ' * '",
+            "' * ' W ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    );
+
+    let moves = get_moves(&board, Square::at(4, 3));
+
+    assert_eq!(
+        moves,
+        vec![Movement {
+            movement_type: MovementType::Forced,
+            row: 2,
+            col: 1
+        }]
+    );
+}
+
+#[test]
+fn italian_prefers_capturing_a_king_first() {
+    let board = utils::make_board_for(
+        &Italian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' b ' * ' B '",
+            "' * ' * ' * ' *",
+            "* ' B ' b ' * '",
+            "' * ' W ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    );
+
+    let sequences = get_sequences(&board, Square::at(4, 3));
+
+    assert_eq!(
+        sequences,
+        vec![MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(2, 1), (0, 3)],
+            captures: positions![(3, 2), (1, 2)],
+        }]
+    );
+}
+
+#[test]
+fn brazilian_majority_capture_is_mandatory() {
+    let board = utils::make_board_for(
+        &Brazilian,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' w ' * ' b '",
+            "' * ' * ' * ' w",
+            "* ' * ' * ' * '",
+        ],
+    );
+
+    let pieces: Vec<_> = get_legal_sequences(&board, Color::White)
+        .into_iter()
+        .map(|(p, s)| (p.row, p.col, s.captures.len()))
+        .collect();
+
+    assert_eq!(pieces, vec![(5, 2, 2)]);
+}
+
+#[test]
+fn pool_men_capture_backwards_and_choose_freely() {
+    let board = utils::make_board_for(
+        &Pool,
+        &[
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' b ' * ' *",
+            "* ' w ' * ' * '",
+            "' b ' * ' * ' *",
+            "* ' * ' * ' * '",
+        ],
+    );
+
+    let sequences = get_sequences(&board, Square::at(5, 2));
+
+    assert_eq!(
+        sequences,
+        vec![
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(3, 4), (1, 2)],
+                captures: positions![(4, 3), (2, 3)],
+            },
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(7, 0)],
+                captures: positions![(6, 1)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn spanish_is_played_on_a_flipped_board() {
+    let board = Board::for_rules(&Spanish);
+
+    assert_eq!(board.get(Square::at(0, 0)).unwrap().color, Color::Black);
+    assert!(board.get(Square::at(0, 1)).is_none());
+    assert_eq!(board.get(Square::at(7, 7)).unwrap().color, Color::White);
+    assert_eq!(board.count_pieces(Color::White), 12);
+
+    let moves = get_moves(&board, Square::at(5, 1));
+    let squares: Vec<_> = moves.iter().map(|m| (m.row, m.col)).collect();
+    assert_eq!(squares, vec![(4, 0), (4, 2)]);
+}
+
+#[test]
+fn spanish_prefers_capturing_the_most_kings() {
+    let board = utils::make_board_for(
+        &Spanish,
+        &[
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+            "* ' b ' B ' * '",
+            "' * ' w ' * ' *",
+            "* ' * ' * ' * '",
+            "' * ' * ' * ' *",
+        ],
+    );
+
+    let moves = get_moves(&board, Square::at(5, 3));
+
+    assert_eq!(
+        moves,
+        vec![Movement {
+            movement_type: MovementType::Forced,
+            row: 3,
+            col: 5
+        }]
+    );
+}
+
+#[test]
+fn turkish_is_played_on_every_square() {
+    let board = Board::for_rules(&Turkish);
+
+    assert!(board.get(Square::at(0, 0)).is_none());
+    assert_eq!(board.get(Square::at(1, 0)).unwrap().color, Color::Black);
+    assert_eq!(board.get(Square::at(6, 7)).unwrap().color, Color::White);
+    assert_eq!(board.count_pieces(Color::Black), 16);
+    assert_eq!(board.count_pieces(Color::White), 16);
+
+    let moves = get_moves(&board, Square::at(5, 3));
+    let squares: Vec<_> = moves.iter().map(|m| (m.row, m.col)).collect();
+    assert_eq!(squares, vec![(4, 3)]);
+}
+
+#[test]
+fn turkish_men_move_forward_and_sideways() {
+    let board = utils::make_board_for(
+        &Turkish,
+        &[
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * w * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+        ],
+    );
+
+    let moves = get_moves(&board, Square::at(4, 4));
+    let squares: Vec<_> = moves.iter().map(|m| (m.row, m.col)).collect();
+    assert_eq!(squares, vec![(3, 4), (4, 3), (4, 5)]);
+}
+
+#[test]
+fn turkish_king_passes_over_pieces_it_has_captured() {
+    let board = utils::make_board_for(
+        &Turkish,
+        &[
+            "* * * * * * * *",
+            "* * * b * * * *",
+            "* W * b * * * *",
+            "* * * * * * * *",
+            "* * * * * b * *",
+            "* * * * b * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+        ],
+    );
+
+    let sequences = get_sequences(&board, Square::at(2, 1));
+
+    assert_eq!(
+        sequences,
+        vec![MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(2, 5), (5, 5), (5, 3), (0, 3)],
+            captures: positions![(2, 3), (4, 5), (5, 4), (1, 3)],
+        }]
+    );
+}
+
+#[test]
+fn turkish_king_can_not_turn_back_during_a_capture() {
+    let board = utils::make_board_for(
+        &Turkish,
+        &[
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* b * W * b * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+            "* * * * * * * *",
+        ],
+    );
+
+    let sequences = get_sequences(&board, Square::at(4, 3));
+
+    assert_eq!(
+        sequences,
+        vec![
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(4, 0)],
+                captures: positions![(4, 1)],
+            },
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(4, 6)],
+                captures: positions![(4, 5)],
+            },
+            MoveSequence {
+                movement_type: MovementType::Forced,
+                path: positions![(4, 7)],
+                captures: positions![(4, 5)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn frisian_men_capture_orthogonally() {
+    let board = utils::make_board_for(
+        &Frisian,
+        &[
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' w ' b ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+            "' * ' * ' * ' * ' *",
+            "* ' * ' * ' * ' * '",
+        ],
+    );
+
+    let sequences = get_sequences(&board, Square::at(4, 5));
+
+    assert_eq!(
+        sequences,
+        vec![MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(4, 9)],
+            captures: positions![(4, 7)],
+        }]
+    );
+}
+
+#[test]
+fn frisian_prefers_the_capture_worth_the_most() {
+    let diagram = [
+        "' * ' * ' * ' * ' *",
+        "* ' * ' * ' * ' * '",
+        "' * ' * ' * ' * ' *",
+        "* ' * ' * ' * ' * '",
+        "' * ' * ' w ' b ' *",
+        "* ' * ' B ' * ' * '",
+        "' * ' * ' * ' * ' *",
+        "* ' * ' * ' * ' * '",
+        "' * ' * ' * ' * ' *",
+        "* ' * ' * ' * ' * '",
+    ];
+    let board = utils::make_board_for(&Frisian, &diagram);
+
+    let moves: Vec<_> = get_moves(&board, Square::at(4, 5))
+        .iter()
+        .map(|m| (m.row, m.col))
+        .collect();
+    assert_eq!(moves, vec![(6, 3)]);
+
+    let mut diagram = diagram;
+    diagram[2] = "' * ' * ' * ' * ' b";
+    let board = utils::make_board_for(&Frisian, &diagram);
+
+    let moves: Vec<_> = get_moves(&board, Square::at(4, 5))
+        .iter()
+        .map(|m| (m.row, m.col))
+        .collect();
+    assert_eq!(moves, vec![(4, 9)]);
+}
+
+#[test]
+fn crowning_ends_the_turn_by_default() {
+    let mut board = utils::make_board([
+        "' * ' * ' * ' *",
+        "* ' b ' b ' * '",
+        "' w ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+        "' * ' * ' * ' *",
+        "* ' * ' * ' * '",
+    ]);
+    board.rules = &BackwardsMen;
+
+    assert_eq!(
+        get_sequences(&board, Square::at(2, 1)),
+        vec![MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(0, 3)],
+            captures: positions![(1, 2)],
+        }]
+    );
+
+    board.rules = &Pool;
+
+    assert_eq!(
+        get_sequences(&board, Square::at(2, 1)),
+        vec![MoveSequence {
+            movement_type: MovementType::Forced,
+            path: positions![(0, 3), (2, 5)],
+            captures: positions![(1, 2), (1, 4)],
+        }]
+    );
+}
+
+#[test]
+fn transforms_legal_moves_along_with_the_board() {
+    use crate::board::Symmetry;
+
+    let sorted = |moves: Vec<LegalMove>| {
+        let mut moves: Vec<Vec<u8>> = moves.into_iter().map(Vec::from).collect();
+        moves.sort();
+        moves
+    };
+    for rules in RULESETS.iter() {
+        let mut board = Board::for_rules(*rules);
+        for _ in 0..12 {
+            for &symmetry in [Symmetry::Mirror, Symmetry::Rotate].iter() {
+                let transformed = match board.transformed(symmetry) {
+                    Some(transformed) => transformed,
+                    None => continue,
+                };
+                let expected = board
+                    .legal_moves(board.turn())
+                    .iter()
+                    .map(|m| m.transformed(*rules, symmetry))
+                    .collect();
+                assert_eq!(
+                    sorted(transformed.legal_moves(transformed.turn())),
+                    sorted(expected),
+                    "{} {:?}\n{}",
+                    rules.name(),
+                    symmetry,
+                    board
+                );
+            }
+            let moves = board.legal_moves(board.turn());
+            let next = match moves.last() {
+                Some(next) => next,
+                None => break,
+            };
+            board = board.make_move(next.origin, next.sequence.path[0]).unwrap();
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::ops::Range;
+
+use super::{
+    get_longest_captures, get_orthogonal_directions, Crowning, Direction, MoveSequence, RuleSet,
+};
+use crate::board::{Board, Color, GamePiece, Layout};
+
+/// Turkish draughts: played on every square of the board, with sixteen men a
+/// side starting on the second and third rows. Men move and capture forward
+/// and sideways, kings fly in all four orthogonal directions. Captured pieces
+/// are removed at once and the capture that takes the most pieces has to be
+/// played.
+pub struct Turkish;
+
+impl RuleSet for Turkish {
+    fn name(&self) -> &'static str {
+        "turkish"
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::Full
+    }
+
+    fn move_directions(&self, piece: &GamePiece) -> Vec<(Direction, Direction)> {
+        use Direction::*;
+        if piece.is_king {
+            return get_orthogonal_directions();
+        }
+        let forward = match piece.color {
+            Color::White => Decrease,
+            Color::Black => Increase,
+        };
+        vec![(forward, Keep), (Keep, Decrease), (Keep, Increase)]
+    }
+
+    fn has_flying_kings(&self) -> bool {
+        true
+    }
+
+    fn crowning(&self) -> Crowning {
+        Crowning::PassesThrough
+    }
+
+    fn order_captures(
+        &self,
+        _board: &Board,
+        captures: Vec<(GamePiece, MoveSequence)>,
+    ) -> Vec<(GamePiece, MoveSequence)> {
+        get_longest_captures(captures)
+    }
+
+    fn starting_rows(&self, color: Color) -> Range<usize> {
+        match color {
+            Color::Black => 1..3,
+            Color::White => 5..7,
+        }
+    }
+
+    fn removes_captures_immediately(&self) -> bool {
+        true
+    }
+}
@@ -9,12 +9,27 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+/// Converts a row, a column or a number of squares to a byte.
+///
+/// Boards have at most 64 playable squares and 11 rows, see
+/// `RuleSet::size`, so every such value fits.
+pub(crate) fn to_byte(value: usize) -> u8 {
+    debug_assert!(
+        value <= u8::MAX as usize,
+        "{} does not fit in a byte",
+        value
+    );
+    value as u8
+}
+
 /// Takes a board definition as an array of strings and creates a board.
 ///
 /// "*" means a black square, "'" means a white square
 /// "w" is a white piece, "W" is a white king,
 /// "b" is a black piece, "B" is a black king.
 ///
+/// White is the side to move and the game is played by American rules.
+///
 /// # Examples
 ///
 /// ```rust
@@ -32,45 +47,16 @@ pub fn set_panic_hook() {
 /// ```
 #[cfg(test)]
 pub(crate) fn make_board(board: [&str; 8]) -> crate::board::Board {
-    use crate::board::{Board, Color, Piece};
+    make_board_for(&crate::rules::American, &board)
+}
 
-    let mut squares = [
-        [None; 4], [None; 4], [None; 4], [None; 4], [None; 4], [None; 4], [None; 4], [None; 4],
-    ];
-    for (r, row) in board.iter().enumerate() {
-        for (c, symbol) in row
-            .split_ascii_whitespace()
-            .enumerate()
-            .filter(|(c, _)| (c + r + 1) % 2 == 0)
-        {
-            match symbol {
-                "w" => {
-                    squares[r][c / 2] = Some(Piece {
-                        color: Color::White,
-                        is_king: false,
-                    })
-                }
-                "W" => {
-                    squares[r][c / 2] = Some(Piece {
-                        color: Color::White,
-                        is_king: true,
-                    })
-                }
-                "b" => {
-                    squares[r][c / 2] = Some(Piece {
-                        color: Color::Black,
-                        is_king: false,
-                    })
-                }
-                "B" => {
-                    squares[r][c / 2] = Some(Piece {
-                        color: Color::Black,
-                        is_king: true,
-                    })
-                }
-                _ => {}
-            }
-        }
-    }
-    Board { squares }
+/// Takes a board definition like `make_board` does and creates a board
+/// played by the given rules. The definition has to match the size of the
+/// board the rules are played on, see `Board::read_rows`.
+#[cfg(test)]
+pub(crate) fn make_board_for(
+    rules: &'static dyn crate::rules::RuleSet,
+    board: &[&str],
+) -> crate::board::Board {
+    crate::board::Board::read_rows(rules, board).unwrap()
 }
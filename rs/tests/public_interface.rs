@@ -1,6 +1,11 @@
+use std::collections::HashSet;
+
 use wasm_bindgen_test::*;
 
-use checkers_ponderer::{Board, Color};
+use checkers_ponderer::{
+    get_rules, Board, Color, DrawReason, DrawRules, Game, GameResult, InvalidMove, Predictor,
+    Setup, Square, Symmetry,
+};
 
 #[wasm_bindgen_test]
 fn get_initial_moves() {
@@ -90,6 +95,14 @@ fn get_pieces() {
     }
 }
 
+#[wasm_bindgen_test]
+fn get_off_the_board() {
+    let board = Board::new();
+
+    assert!(board.get(0, 9).is_none());
+    assert!(board.get(9, 0).is_none());
+}
+
 #[wasm_bindgen_test]
 fn get_movable_pieces() {
     let board = Board::new();
@@ -103,3 +116,218 @@ fn get_movable_pieces() {
         assert_eq!(piece[1], false as u8);
     }
 }
+
+#[wasm_bindgen_test]
+fn get_sequences_for_a_piece() {
+    let mut board = Board::new();
+    board.make_move(5, 2, 4, 1).unwrap();
+    board.make_move(2, 3, 3, 2).unwrap();
+
+    let sequences = board.sequences_for(4, 1);
+
+    // movement type, path length, path, capture count, captures
+    assert_eq!(sequences, vec![1, 1, 2, 3, 1, 3, 2]);
+}
+
+#[wasm_bindgen_test]
+fn turn_alternates_between_moves() {
+    let mut board = Board::new();
+    assert_eq!(board.turn(), Color::White);
+
+    board.make_move(5, 2, 4, 1).unwrap();
+    assert_eq!(board.turn(), Color::Black);
+    let error = board.make_move(5, 4, 4, 3).unwrap_err();
+    assert_eq!(error.code(), InvalidMove::WrongTurn);
+}
+
+#[wasm_bindgen_test]
+fn rejects_squares_off_the_board() {
+    let mut board = Board::new();
+    assert!(board.get(8, 255).is_none());
+    assert!(board.moves_for(200, 1).is_empty());
+
+    let error = board.make_move(5, 2, 255, 255).unwrap_err();
+    assert_eq!(error.code(), InvalidMove::OffBoard);
+    let error = board.huff(9, 0).unwrap_err();
+    assert_eq!(error.code(), InvalidMove::OffBoard);
+}
+
+#[wasm_bindgen_test]
+fn new_game_is_ongoing() {
+    let board = Board::new();
+
+    assert_eq!(board.result(), GameResult::Ongoing);
+    assert_eq!(board.draw_reason(), None);
+}
+
+#[wasm_bindgen_test]
+fn select_rules_by_name() {
+    let board = Board::with_rules("american").unwrap();
+    assert_eq!(board.rules_name(), "american");
+
+    assert!(Board::with_rules("unknown").is_err());
+}
+
+#[wasm_bindgen_test]
+fn play_international_draughts() {
+    let mut board = Board::with_rules("international").unwrap();
+
+    assert_eq!(board.size(), 10);
+    assert_eq!(board.count_pieces(Color::White), 20);
+    assert!(board.make_move(6, 1, 5, 2).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn predict_russian_draughts() {
+    let board = Board::with_rules("russian").unwrap();
+    assert_eq!(board.rules_name(), "russian");
+
+    let mut predictor = Predictor::new(board, 1).unwrap();
+    let next_move = predictor.get_next_move().unwrap();
+
+    assert_eq!(next_move[0], 5);
+}
+
+#[wasm_bindgen_test]
+fn rejects_a_predictor_without_depth() {
+    let error = Predictor::new(Board::new(), 0).err().unwrap();
+    assert_eq!(error.depth(), 0);
+    assert_eq!(error.message(), "The depth must be between 1 and 127");
+}
+
+#[wasm_bindgen_test]
+fn select_8x8_variants_by_name() {
+    for name in &["brazilian", "pool", "spanish"] {
+        let board = Board::with_rules(name).unwrap();
+
+        assert_eq!(board.size(), 8);
+        assert_eq!(board.count_pieces(Color::Black), 12);
+    }
+}
+
+#[wasm_bindgen_test]
+fn prints_and_reads_back_a_diagram() {
+    let mut board = Board::with_rules("pool").unwrap();
+    board.make_move(5, 2, 4, 3).unwrap();
+
+    let copy = Board::from_diagram(&board.diagram()).unwrap();
+    assert_eq!(copy.rules_name(), "pool");
+    assert_eq!(copy.turn(), Color::Black);
+    assert_eq!(copy.all_pieces(), board.all_pieces());
+    assert!(Board::from_diagram("' *").is_err());
+}
+
+#[wasm_bindgen_test]
+fn a_lower_move_limit_ends_the_game_sooner() {
+    let diagram = "
+        ' * ' * ' * ' *
+        * ' * ' * ' * '
+        ' B ' * ' * ' *
+        * ' * ' * ' * '
+        ' * ' * ' * ' *
+        * ' * ' * ' * '
+        ' * ' * ' * ' W
+        * ' * ' * ' * '
+    ";
+    let mut board = Board::from_diagram(diagram).unwrap();
+    let mut limited = Board::from_diagram(diagram).unwrap();
+    limited.set_draw_rules(None, Some(1));
+    assert_eq!(limited.repetitions(), None);
+    assert_eq!(limited.move_limit(), Some(1));
+
+    for board in [&mut board, &mut limited] {
+        board.make_move(6, 7, 5, 6).unwrap();
+        board.make_move(2, 1, 3, 2).unwrap();
+    }
+
+    assert_eq!(board.result(), GameResult::Ongoing);
+    assert_eq!(limited.result(), GameResult::Draw);
+    assert_eq!(limited.draw_reason(), Some(DrawReason::MoveLimit));
+}
+
+#[wasm_bindgen_test]
+fn play_with_numbered_and_named_squares() {
+    let mut board = Board::new();
+
+    assert_eq!(board.square_number(5, 0), Some(21));
+    assert_eq!(board.square_name(5, 0).as_deref(), Some("a3"));
+    board.make_numbered_move(21, 17).unwrap();
+    board.make_named_move("b6", "a5").unwrap();
+
+    assert!(board.get(4, 1).is_some());
+    assert!(board.get_numbered(17).is_some());
+    assert!(board.get_named("a5").is_some());
+    assert!(board.get_numbered(33).is_none());
+    assert!(board.make_numbered_move(0, 1).is_err());
+}
+
+#[wasm_bindgen_test]
+fn transform_the_board_by_its_symmetries() {
+    let board = Board::new();
+
+    assert!(board.mirrored().is_none());
+    assert!(board.transformed(Symmetry::MirrorRotate).is_none());
+    let rotated = board.rotated();
+    assert_eq!(rotated.turn(), Color::Black);
+    assert_eq!(board.transform_square(Symmetry::Rotate, 5, 0), vec![2, 7]);
+    // Every opening move is a single step, seven bytes long.
+    let sorted = |moves: Vec<u8>| {
+        let mut moves: Vec<_> = moves.chunks(7).map(<[u8]>::to_vec).collect();
+        moves.sort();
+        moves
+    };
+    assert_eq!(
+        sorted(board.transformed_legal_moves(Color::White, Symmetry::Rotate)),
+        sorted(rotated.legal_moves(Color::Black))
+    );
+    assert_eq!(board.canonical_symmetry(), Symmetry::Identity);
+    assert_eq!(rotated.canonical().diagram(), board.diagram());
+
+    let board = Board::with_rules("turkish").unwrap();
+    assert!(board.mirrored().is_some());
+}
+
+#[wasm_bindgen_test]
+fn play_a_game_from_rust() {
+    let rules = get_rules("russian").unwrap();
+    let mut game = Game::for_setup(rules, Setup::new(Color::Black, Color::White));
+    game.set_rules(DrawRules {
+        repetitions: None,
+        move_limit: Some(20),
+    });
+
+    let from = Square::new(rules, 5, 2).unwrap();
+    let to = Square::new(rules, 4, 1).unwrap();
+    game.make_move(from, to).unwrap();
+
+    assert_eq!(game.board().turn(), Color::White);
+    assert_eq!(
+        game.board().get(to).map(|piece| piece.color),
+        Some(Color::Black)
+    );
+    assert_eq!(game.rules().move_limit, Some(20));
+    assert_eq!(game.result(), GameResult::Ongoing);
+}
+
+#[wasm_bindgen_test]
+fn boards_with_the_same_position_are_equal() {
+    let mut board = Board::new();
+    let mut transposed = Board::new();
+    for &(from_row, from_col, to_row, to_col) in
+        [(5, 0, 4, 1), (2, 1, 3, 0), (5, 6, 4, 7), (2, 3, 3, 4)].iter()
+    {
+        board.make_move(from_row, from_col, to_row, to_col).unwrap();
+    }
+    for &(from_row, from_col, to_row, to_col) in
+        [(5, 6, 4, 7), (2, 3, 3, 4), (5, 0, 4, 1), (2, 1, 3, 0)].iter()
+    {
+        transposed
+            .make_move(from_row, from_col, to_row, to_col)
+            .unwrap();
+    }
+
+    assert!(board == transposed);
+    assert!(board != Board::new());
+    let boards: HashSet<_> = vec![Board::new(), board, transposed].into_iter().collect();
+    assert_eq!(boards.len(), 2);
+}
@@ -27,3 +27,10 @@ export interface Position {
   row: number;
   col: number;
 }
+
+export interface LegalMove {
+  origin: Position;
+  type: MovementType;
+  path: Position[];
+  captures: Position[];
+}
@@ -1,4 +1,5 @@
-import { BoardSquare } from "@/interfaces";
+import { BoardSquare, LegalMove, Position } from "@/interfaces";
+import { MovementType } from "engine";
 
 export const generateStartingBoard = (): BoardSquare[][] => {
   const board: BoardSquare[][] = [];
@@ -12,3 +13,24 @@ export const generateStartingBoard = (): BoardSquare[][] => {
   }
   return board;
 };
+
+export const parseLegalMoves = (bytes: Uint8Array): LegalMove[] => {
+  const moves: LegalMove[] = [];
+  let i = 0;
+  const readPositions = (): Position[] => {
+    const positions: Position[] = [];
+    const count = bytes[i++];
+    for (let n = 0; n < count; n++) {
+      positions.push({ row: bytes[i++], col: bytes[i++] });
+    }
+    return positions;
+  };
+  while (i < bytes.length) {
+    const origin = { row: bytes[i++], col: bytes[i++] };
+    const type = bytes[i++] as MovementType;
+    const path = readPositions();
+    const captures = readPositions();
+    moves.push({ origin, type, path, captures });
+  }
+  return moves;
+};